@@ -0,0 +1,71 @@
+//! Derive macros for frunk_utils.
+
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::{parse_macro_input, spanned::Spanned, Data, DeriveInput, Index};
+
+/// Generate inherent `map_fields`, `for_each_field` and `fields_iter` methods on a struct which also derives
+/// `frunk::Generic`, forwarding to the `frunk_utils` traversals so that they can be called without importing the
+/// extension traits.
+#[proc_macro_derive(Fieldwise)]
+pub fn derive_fieldwise(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    let Data::Struct(data) = &input.data else {
+        return syn::Error::new(input.span(), "Fieldwise can only be derived for structs")
+            .to_compile_error()
+            .into();
+    };
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let field_types = data
+        .fields
+        .iter()
+        .map(|field| &field.ty)
+        .collect::<Vec<_>>();
+    let field_accessors = data
+        .fields
+        .iter()
+        .enumerate()
+        .map(|(i, field)| match &field.ident {
+            Some(ident) => quote!(#ident),
+            None => {
+                let index = Index::from(i);
+                quote!(#index)
+            }
+        })
+        .collect::<Vec<_>>();
+    quote! {
+        impl #impl_generics #name #ty_generics #where_clause {
+            /// Map each field through `f` to build a similarly-shaped struct
+            pub fn map_fields<__U: ::frunk::Generic, __F>(self, f: __F) -> __U
+            where
+                <Self as ::frunk::Generic>::Repr:
+                    ::frunk::hlist::HMappable<::frunk_utils::Poly<__F>, Output = __U::Repr>,
+            {
+                ::frunk_utils::WithGeneric::hmap(self, f)
+            }
+
+            /// Call `f` on each field by value
+            pub fn for_each_field<__F>(self, f: __F)
+            where
+                <Self as ::frunk::Generic>::Repr: ::frunk_utils::ForEach<__F>,
+            {
+                ::frunk_utils::WithGeneric::for_each(self, f)
+            }
+
+            /// Iterate over references to the fields if they all have the same type
+            pub fn fields_iter<'__a, __U: '__a>(&'__a self) -> impl Iterator<Item = &'__a __U>
+            where
+                ::frunk::HList!(#(&'__a #field_types),*):
+                    ::frunk_utils::MapToList<::frunk_utils::Identity, &'__a __U>,
+            {
+                ::frunk_utils::MapToList::map_to_list(
+                    ::frunk::hlist![#(&self.#field_accessors),*],
+                    ::frunk_utils::Identity,
+                )
+                .into_iter()
+            }
+        }
+    }
+    .into()
+}
@@ -0,0 +1,222 @@
+//! A type-state builder for any [Generic] struct. The builder's type records which fields have been supplied, so
+//! [build](Builder::build) is only available once every field has been, and supplying the same field twice fails to
+//! compile.
+//!
+//! Fields are identified by their type. When two fields share a type and are both still missing, the one to fill has
+//! to be picked with an explicit [Here]/[There] index as the second type parameter of [set](Builder::set).
+//!
+//! ```compile_fail
+//! use frunk::Generic;
+//! use frunk_utils::WithGeneric;
+//!
+//! #[derive(Generic)]
+//! struct Point {
+//!     x: i32,
+//!     name: String,
+//! }
+//!
+//! // `name` was never supplied
+//! let point: Point = Point::builder().set(1i32).build();
+//! ```
+//!
+//! [Here]: frunk::indices::Here
+//! [There]: frunk::indices::There
+
+use std::{fmt, marker::PhantomData};
+
+use frunk::{
+    indices::{Here, There},
+    prelude::HList,
+    Generic, HCons, HNil,
+};
+
+/// A field that hasn't been supplied yet
+pub struct Missing<T>(PhantomData<T>);
+
+/// A field that has been supplied
+pub struct Present<T>(pub T);
+
+/// Maps the HList repr of a struct to the builder state in which none of its fields have been supplied
+pub trait Unfilled: HList {
+    type Slots: HList;
+
+    fn slots() -> Self::Slots;
+}
+
+impl Unfilled for HNil {
+    type Slots = HNil;
+
+    fn slots() -> Self::Slots {
+        HNil
+    }
+}
+
+impl<T, Tail: Unfilled> Unfilled for HCons<T, Tail> {
+    type Slots = HCons<Missing<T>, Tail::Slots>;
+
+    fn slots() -> Self::Slots {
+        HCons {
+            head: Missing(PhantomData),
+            tail: Tail::slots(),
+        }
+    }
+}
+
+/// Supply the missing field of type `T` found at `Index`
+pub trait Fill<T, Index>: HList {
+    type Output: HList;
+
+    fn fill(self, value: T) -> Self::Output;
+}
+
+impl<T, Tail: HList> Fill<T, Here> for HCons<Missing<T>, Tail> {
+    type Output = HCons<Present<T>, Tail>;
+
+    fn fill(self, value: T) -> Self::Output {
+        HCons {
+            head: Present(value),
+            tail: self.tail,
+        }
+    }
+}
+
+impl<T, Head, Tail: Fill<T, Index>, Index> Fill<T, There<Index>> for HCons<Head, Tail> {
+    type Output = HCons<Head, Tail::Output>;
+
+    fn fill(self, value: T) -> Self::Output {
+        HCons {
+            head: self.head,
+            tail: self.tail.fill(value),
+        }
+    }
+}
+
+/// Implemented by builder states in which every field has been supplied
+pub trait Complete: HList {
+    type Repr: HList;
+
+    fn complete(self) -> Self::Repr;
+}
+
+impl Complete for HNil {
+    type Repr = HNil;
+
+    fn complete(self) -> Self::Repr {
+        HNil
+    }
+}
+
+impl<T, Tail: Complete> Complete for HCons<Present<T>, Tail> {
+    type Repr = HCons<T, Tail::Repr>;
+
+    fn complete(self) -> Self::Repr {
+        HCons {
+            head: self.head.0,
+            tail: self.tail.complete(),
+        }
+    }
+}
+
+/// Builds a `T` one field at a time. `Slots` is an HList of [Missing] and [Present] parallel to `T`'s repr.
+pub struct Builder<T, Slots> {
+    slots: Slots,
+    marker: PhantomData<fn() -> T>,
+}
+
+impl<T: Generic> Builder<T, <T::Repr as Unfilled>::Slots>
+where
+    T::Repr: Unfilled,
+{
+    /// A builder with none of the fields supplied
+    pub fn new() -> Self {
+        Builder {
+            slots: T::Repr::slots(),
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<T: Generic> Default for Builder<T, <T::Repr as Unfilled>::Slots>
+where
+    T::Repr: Unfilled,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Generic, Slots: HList> Builder<T, Slots> {
+    /// Supply the field of type `U`, which must not have been supplied already
+    pub fn set<U, Index>(self, value: U) -> Builder<T, Slots::Output>
+    where
+        Slots: Fill<U, Index>,
+    {
+        Builder {
+            slots: self.slots.fill(value),
+            marker: PhantomData,
+        }
+    }
+
+    /// Assemble the struct. Only available once every field has been supplied.
+    pub fn build(self) -> T
+    where
+        Slots: Complete<Repr = T::Repr>,
+    {
+        T::from(self.slots.complete())
+    }
+}
+
+impl<T, Slots> fmt::Debug for Builder<T, Slots> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Builder")
+            .field("type", &std::any::type_name::<T>())
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use frunk::Generic;
+
+    use super::*;
+    use crate::WithGeneric;
+
+    #[derive(Generic, Debug, PartialEq)]
+    struct Request {
+        path: String,
+        retries: u8,
+        verbose: bool,
+    }
+
+    #[test]
+    fn any_order() {
+        let request = Request::builder()
+            .set(true)
+            .set("/index".to_string())
+            .set(3u8)
+            .build();
+        assert_eq!(
+            request,
+            Request {
+                path: "/index".to_string(),
+                retries: 3,
+                verbose: true,
+            }
+        );
+    }
+
+    #[derive(Generic, Debug, PartialEq)]
+    struct Range {
+        start: u32,
+        end: u32,
+    }
+
+    #[test]
+    fn same_typed_fields() {
+        let range = Builder::<Range, _>::new()
+            .set::<_, There<Here>>(10u32)
+            .set(2u32)
+            .build();
+        assert_eq!(range, Range { start: 2, end: 10 });
+    }
+}
@@ -0,0 +1,199 @@
+//! Adapters for building a [Func] out of other `Func`s and closures, analogous to the adapters on [Iterator]
+
+use std::{collections::HashMap, hash::Hash};
+
+use crate::Func;
+
+/// Feed the output of one [Func] into another. Created by [FuncExt::then].
+pub struct Then<F, G> {
+    first: F,
+    second: G,
+}
+
+impl<F: Func<I>, G: Func<F::Output>, I> Func<I> for Then<F, G> {
+    type Output = G::Output;
+
+    fn call(&mut self, i: I) -> Self::Output {
+        self.second.call(self.first.call(i))
+    }
+}
+
+/// Transform the output of a [Func] with a closure. Created by [FuncExt::map_output].
+pub struct MapOutput<F, H> {
+    f: F,
+    h: H,
+}
+
+impl<F: Func<I>, H: FnMut(F::Output) -> O, I, O> Func<I> for MapOutput<F, H> {
+    type Output = O;
+
+    fn call(&mut self, i: I) -> Self::Output {
+        (self.h)(self.f.call(i))
+    }
+}
+
+/// Transform the input of a [Func] with a closure. Created by [FuncExt::premap].
+pub struct Premap<F, H> {
+    f: F,
+    h: H,
+}
+
+impl<F: Func<I>, H: FnMut(J) -> I, I, J> Func<J> for Premap<F, H> {
+    type Output = F::Output;
+
+    fn call(&mut self, j: J) -> Self::Output {
+        self.f.call((self.h)(j))
+    }
+}
+
+/// Caches the outputs of a [Func] by a key extracted from each input, so inputs with a key that has already been seen
+/// are answered from the cache without calling the underlying `Func` again. Since `key` is a closure, this only applies
+/// to a single input type.
+pub struct Memoized<F, K, Key, O> {
+    f: F,
+    key: K,
+    cache: HashMap<Key, O>,
+}
+
+impl<F, K, Key, O> Memoized<F, K, Key, O> {
+    pub fn new(f: F, key: K) -> Self {
+        Memoized {
+            f,
+            key,
+            cache: HashMap::new(),
+        }
+    }
+
+    /// Forget every cached output
+    pub fn clear(&mut self) {
+        self.cache.clear()
+    }
+}
+
+impl<F: Func<I, Output = O>, K: FnMut(&I) -> Key, Key: Eq + Hash, O: Clone, I> Func<I>
+    for Memoized<F, K, Key, O>
+{
+    type Output = O;
+
+    fn call(&mut self, i: I) -> Self::Output {
+        let key = (self.key)(&i);
+        self.cache
+            .entry(key)
+            .or_insert_with(|| self.f.call(i))
+            .clone()
+    }
+}
+
+/// Combinators for [Func]s. These live on a separate trait because a `Func` generally implements [Func] for several
+/// input types, so calling them through `Func<I>` would leave `I` ambiguous.
+pub trait FuncExt: Sized {
+    /// Call `g` on the output of `self`
+    fn then<G>(self, g: G) -> Then<Self, G> {
+        Then {
+            first: self,
+            second: g,
+        }
+    }
+
+    /// Call `h` on the output of `self`. Since `h` is a closure, this only applies where `self` produces a single
+    /// output type.
+    fn map_output<H>(self, h: H) -> MapOutput<Self, H> {
+        MapOutput { f: self, h }
+    }
+
+    /// Call `h` on each input before passing it to `self`. Since `h` is a closure, this only applies to a single
+    /// input type.
+    fn premap<H>(self, h: H) -> Premap<Self, H> {
+        Premap { f: self, h }
+    }
+}
+
+impl<F> FuncExt for F {}
+
+#[cfg(test)]
+mod tests {
+    use frunk::Generic;
+
+    use super::*;
+    use crate::{FnPoly, WithGeneric};
+
+    #[derive(Generic, Debug, PartialEq)]
+    struct Pair<A, B> {
+        a: A,
+        b: B,
+    }
+
+    struct Widen;
+
+    impl<T: Into<i64>> Func<T> for Widen {
+        type Output = i64;
+
+        fn call(&mut self, i: T) -> i64 {
+            i.into()
+        }
+    }
+
+    struct Show;
+
+    impl<T: ToString> Func<T> for Show {
+        type Output = String;
+
+        fn call(&mut self, i: T) -> String {
+            i.to_string()
+        }
+    }
+
+    #[test]
+    fn memoized() {
+        let mut calls = 0;
+        let mut f = Memoized::new(
+            FnPoly(|x: u32| {
+                calls += 1;
+                x * 2
+            }),
+            |x: &u32| *x,
+        );
+        let first: Pair<u32, u32> = Pair { a: 1, b: 2 }.hmap(&mut f);
+        let second: Pair<u32, u32> = Pair { a: 2, b: 1 }.hmap(&mut f);
+        assert_eq!(first, Pair { a: 2, b: 4 });
+        assert_eq!(second, Pair { a: 4, b: 2 });
+        f.clear();
+        let _: Pair<u32, u32> = Pair { a: 1, b: 1 }.hmap(&mut f);
+        drop(f);
+        assert_eq!(calls, 3);
+    }
+
+    #[test]
+    fn then() {
+        let shown: Pair<String, String> = Pair { a: 1u8, b: -2i32 }.hmap(Widen.then(Show));
+        assert_eq!(
+            shown,
+            Pair {
+                a: "1".to_string(),
+                b: "-2".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn map_output() {
+        let doubled: Pair<i64, i64> = Pair { a: 1u8, b: -2i32 }.hmap(Widen.map_output(|x| x * 2));
+        assert_eq!(doubled, Pair { a: 2, b: -4 });
+    }
+
+    #[test]
+    fn premap() {
+        let lengths: Pair<usize, usize> =
+            Pair { a: "ab", b: "cde" }.hmap(FnPoly(str::len).premap(str::trim));
+        assert_eq!(lengths, Pair { a: 2, b: 3 });
+        let shown: Pair<String, String> =
+            Pair { a: 3u32, b: 4u32 }.hmap(Show.premap(|x: u32| x * x));
+        assert_eq!(
+            shown,
+            Pair {
+                a: "9".to_string(),
+                b: "16".to_string()
+            }
+        );
+    }
+}
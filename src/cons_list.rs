@@ -1,10 +1,50 @@
-use std::{iter::FusedIterator, marker::PhantomData, mem::ManuallyDrop, ops::Range};
+use std::{
+    fmt,
+    hash::{Hash, Hasher},
+    iter::FusedIterator,
+    marker::PhantomData,
+    mem::{ManuallyDrop, MaybeUninit},
+    ops::Range,
+};
+
+#[cfg(feature = "serde")]
+mod serde;
 
 #[repr(C)]
-pub struct Cons<T, Tail>(T, Tail);
+#[derive(Clone, Copy, Default)]
+pub struct Cons<T, Tail>(pub(crate) T, pub(crate) Tail);
 
+/// Holds a zero-length array rather than `PhantomData` so that the end of every list is aligned for `T`, even when the
+/// list is empty.
 #[repr(C)]
-pub struct Nil<T>(PhantomData<T>);
+pub struct Nil<T>(pub(crate) [T; 0]);
+
+impl<T> Clone for Nil<T> {
+    fn clone(&self) -> Self {
+        Nil([])
+    }
+}
+
+impl<T: Copy> Copy for Nil<T> {}
+
+impl<T> Default for Nil<T> {
+    fn default() -> Self {
+        Nil([])
+    }
+}
+
+/// Every cast between a list and `[T; LEN]` relies on `Cons` and `Nil` being laid out like an array. Evaluated in a
+/// `const` block at each such cast, so a change to their representation that breaks this fails to compile.
+const fn assert_array_layout<T, Ts: ConsListT<T>>() {
+    assert!(
+        size_of::<Ts>() == size_of::<T>() * Ts::LEN,
+        "list size differs from the equivalent array"
+    );
+    assert!(
+        align_of::<Ts>() == align_of::<T>(),
+        "list alignment differs from its elements"
+    );
+}
 
 pub trait ConsListT<T> {
     const LEN: usize;
@@ -30,38 +70,465 @@ impl<T, Ts: ConsListT<T>> ConsListT<T> for Cons<T, Ts> {
     const LEN: usize = 1 + Ts::LEN;
 
     unsafe fn take_unchecked(&mut self, i: usize) -> T {
-        debug_assert!(i < Self::LEN, "Index out of bounds");
-        let head = self as *mut Self;
-        let head = head.cast::<T>();
-        let elem = head.add(i);
+        // Checked under Miri as well so that out-of-bounds reads are reported at the call site
+        if cfg!(any(debug_assertions, miri)) {
+            assert!(i < Self::LEN, "Index out of bounds");
+        }
+        const { assert_array_layout::<T, Self>() };
+        // Derived from the pointer to the whole list so that it may reach every element
+        let elem = (self as *mut Self).cast::<T>().add(i);
         std::ptr::read(elem)
     }
 }
 
+/// The length of a list as a [typenum] number, for type-level arithmetic that the `const` [ConsListT::LEN] can't take
+/// part in on stable Rust
+#[cfg(feature = "typenum")]
+pub trait ConsListLen<T>: ConsListT<T> {
+    type Len: typenum::Unsigned;
+}
+
+#[cfg(feature = "typenum")]
+impl<T> ConsListLen<T> for Nil<T> {
+    type Len = typenum::U0;
+}
+
+#[cfg(feature = "typenum")]
+impl<T, Ts: ConsListLen<T>> ConsListLen<T> for Cons<T, Ts>
+where
+    Ts::Len: std::ops::Add<typenum::B1>,
+    typenum::Add1<Ts::Len>: typenum::Unsigned,
+{
+    type Len = typenum::Add1<Ts::Len>;
+}
+
+/// Type-level concatenation of two lists
+pub trait Append<T, Rhs: ConsListT<T>>: ConsListT<T> {
+    type Output: ConsListT<T>;
+
+    fn append(self, rhs: Rhs) -> Self::Output;
+}
+
+impl<T, Rhs: ConsListT<T>> Append<T, Rhs> for Nil<T> {
+    type Output = Rhs;
+
+    fn append(self, rhs: Rhs) -> Self::Output {
+        rhs
+    }
+}
+
+impl<T, Ts: Append<T, Rhs>, Rhs: ConsListT<T>> Append<T, Rhs> for Cons<T, Ts> {
+    type Output = Cons<T, Ts::Output>;
+
+    fn append(self, rhs: Rhs) -> Self::Output {
+        let Cons(head, tail) = self;
+        Cons(head, tail.append(rhs))
+    }
+}
+
+/// The list of the same length with each element mapped from `T` to `U`
+pub trait MapElems<T, U>: ConsListT<T> {
+    type Output: ConsListT<U>;
+
+    fn map_elems<F: FnMut(T) -> U>(self, f: &mut F) -> Self::Output;
+}
+
+impl<T, U> MapElems<T, U> for Nil<T> {
+    type Output = Nil<U>;
+
+    fn map_elems<F: FnMut(T) -> U>(self, _f: &mut F) -> Self::Output {
+        Nil([])
+    }
+}
+
+impl<T, U, Ts: MapElems<T, U>> MapElems<T, U> for Cons<T, Ts> {
+    type Output = Cons<U, Ts::Output>;
+
+    fn map_elems<F: FnMut(T) -> U>(self, f: &mut F) -> Self::Output {
+        let Cons(head, tail) = self;
+        let head = f(head);
+        Cons(head, tail.map_elems(f))
+    }
+}
+
+/// Split a list into a prefix of type `Prefix` and whatever remains
+pub trait SplitAt<T, Prefix: ConsListT<T>>: ConsListT<T> {
+    type Suffix: ConsListT<T>;
+
+    fn split_at(self) -> (Prefix, Self::Suffix);
+}
+
+impl<T, Ts: ConsListT<T>> SplitAt<T, Nil<T>> for Ts {
+    type Suffix = Ts;
+
+    fn split_at(self) -> (Nil<T>, Self::Suffix) {
+        (Nil([]), self)
+    }
+}
+
+impl<T, Prefix: ConsListT<T>, Ts: SplitAt<T, Prefix>> SplitAt<T, Cons<T, Prefix>> for Cons<T, Ts> {
+    type Suffix = Ts::Suffix;
+
+    fn split_at(self) -> (Cons<T, Prefix>, Self::Suffix) {
+        let Cons(head, tail) = self;
+        let (prefix, suffix) = tail.split_at();
+        (Cons(head, prefix), suffix)
+    }
+}
+
+/// Maps an array type `[T; N]` to the list type of the same length. Implemented for `N` up to 32.
+pub trait ArrayToList<T> {
+    type List: ConsListT<T>;
+}
+
 pub struct ConsList<T, Ts: ConsListT<T>> {
-    list: Ts,
-    marker: PhantomData<T>,
+    pub(crate) list: Ts,
+    pub(crate) marker: PhantomData<T>,
 }
 
 impl<T> ConsList<T, Nil<T>> {
-    pub fn nil() -> ConsList<T, Nil<T>> {
+    pub const fn nil() -> ConsList<T, Nil<T>> {
         Self {
-            list: Nil(PhantomData),
+            list: Nil([]),
             marker: PhantomData,
         }
     }
 }
 
 impl<T, Ts: ConsListT<T>> ConsList<T, Cons<T, Ts>> {
-    pub fn cons(head: T, tail: ConsList<T, Ts>) -> ConsList<T, Cons<T, Ts>> {
-        let ConsList { list: tail, marker } = tail;
+    pub const fn cons(head: T, tail: ConsList<T, Ts>) -> ConsList<T, Cons<T, Ts>> {
         ConsList {
+            list: Cons(head, tail.into_list()),
+            marker: PhantomData,
+        }
+    }
+
+    /// Remove the first element, returning it along with the shorter list
+    pub fn pop(self) -> (T, ConsList<T, Ts>) {
+        let ConsList {
             list: Cons(head, tail),
             marker,
+        } = self;
+        (head, ConsList { list: tail, marker })
+    }
+}
+
+impl<T, Ts: ConsListT<T>> ConsList<T, Ts> {
+    /// The number of elements in the list, known at compile time
+    pub const LEN: usize = Ts::LEN;
+
+    /// Unwrap the underlying list. Moving the field out directly isn't accepted in a `const fn` because the checker
+    /// can't see that nothing is left behind to drop.
+    const fn into_list(self) -> Ts {
+        let this = ManuallyDrop::new(self);
+        // SAFETY: `this` is never dropped, so `list` is moved out exactly once
+        unsafe { std::ptr::read(&raw const (*(&raw const this).cast::<Self>()).list) }
+    }
+
+    pub const fn len(&self) -> usize {
+        Ts::LEN
+    }
+
+    pub const fn is_empty(&self) -> bool {
+        Ts::LEN == 0
+    }
+
+    /// Build a list from an array of the same length. Fails to compile if `N` is not the length of the list.
+    pub fn from_array<const N: usize>(arr: [T; N]) -> Self {
+        const {
+            assert!(N == Ts::LEN, "array length must match list length");
+            assert_array_layout::<T, Ts>();
+        };
+        let arr = ManuallyDrop::new(arr);
+        // SAFETY: `Cons` and `Nil` are `repr(C)` so a list of length `N` has the same layout as `[T; N]`, and the
+        // array is wrapped in `ManuallyDrop` so each element is only dropped once.
+        let list = unsafe { std::ptr::read((&raw const *arr).cast::<Ts>()) };
+        ConsList {
+            list,
+            marker: PhantomData,
+        }
+    }
+
+    pub fn as_slice(&self) -> &[T] {
+        const { assert_array_layout::<T, Ts>() };
+        // SAFETY: The list is laid out like `[T; Ts::LEN]`.
+        unsafe { std::slice::from_raw_parts((&raw const self.list).cast::<T>(), Ts::LEN) }
+    }
+
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        const { assert_array_layout::<T, Ts>() };
+        // SAFETY: The list is laid out like `[T; Ts::LEN]`.
+        unsafe { std::slice::from_raw_parts_mut((&raw mut self.list).cast::<T>(), Ts::LEN) }
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, T> {
+        self.as_slice().iter()
+    }
+
+    pub fn iter_mut(&mut self) -> std::slice::IterMut<'_, T> {
+        self.as_mut_slice().iter_mut()
+    }
+
+    /// Convert the list into an array without copying elements one at a time. Fails to compile if `N` is not the
+    /// length of the list.
+    pub fn into_array<const N: usize>(self) -> [T; N] {
+        const {
+            assert!(N == Ts::LEN, "array length must match list length");
+            assert_array_layout::<T, Ts>();
+        };
+        let list = ManuallyDrop::new(self.list);
+        // SAFETY: `Cons` and `Nil` are `repr(C)` so a list of length `N` has the same layout as `[T; N]`, and the
+        // original list is wrapped in `ManuallyDrop` so each element is only dropped once.
+        unsafe { std::ptr::read((&raw const *list).cast::<[T; N]>()) }
+    }
+
+    /// Move the elements into a `Vec` with a single copy
+    pub fn into_vec(self) -> Vec<T> {
+        const { assert_array_layout::<T, Ts>() };
+        let list = ManuallyDrop::new(self.list);
+        let mut vec = Vec::with_capacity(Ts::LEN);
+        // SAFETY: The list is laid out like `[T; Ts::LEN]` and the destination has room for `Ts::LEN` elements.
+        // Ownership of every element moves into the `Vec` and the list itself is never dropped.
+        unsafe {
+            std::ptr::copy_nonoverlapping(
+                (&raw const *list).cast::<T>(),
+                vec.as_mut_ptr(),
+                Ts::LEN,
+            );
+            vec.set_len(Ts::LEN);
+        }
+        vec
+    }
+
+    pub fn into_boxed_slice(self) -> Box<[T]> {
+        self.into_vec().into_boxed_slice()
+    }
+
+    /// Reverse the order of the elements in place
+    pub fn reverse(&mut self) {
+        self.as_mut_slice().reverse()
+    }
+
+    /// Consume the list and return it in reverse order. Every element has the same type, so the reversed list has the
+    /// same type and length as the original.
+    pub fn rev(mut self) -> Self {
+        self.reverse();
+        self
+    }
+
+    /// Add an element to the front of the list
+    pub fn push(self, head: T) -> ConsList<T, Cons<T, Ts>> {
+        ConsList::cons(head, self)
+    }
+
+    /// Map each element from first to last, keeping the length in the type. `f` is called from a single loop over the
+    /// elements, writing each result straight into the new list.
+    pub fn map<U, F: FnMut(T) -> U>(self, mut f: F) -> ConsList<U, Ts::Output>
+    where
+        Ts: MapElems<T, U>,
+    {
+        const { assert_array_layout::<U, Ts::Output>() };
+        let mut list = MaybeUninit::<Ts::Output>::uninit();
+        let mut written = Written {
+            start: list.as_mut_ptr().cast::<U>(),
+            len: 0,
+        };
+        for elem in self {
+            // SAFETY: The output list is laid out like `[U; Ts::LEN]` and the input has the same length, so there is
+            // room for every element. `written` drops the ones already written if `f` panics.
+            unsafe { written.start.add(written.len).write(f(elem)) };
+            written.len += 1;
+        }
+        std::mem::forget(written);
+        ConsList {
+            // SAFETY: Every element of the output list has been written.
+            list: unsafe { list.assume_init() },
+            marker: PhantomData,
+        }
+    }
+
+    /// Split the list into its first `K` elements and the rest, with both lengths known at compile time
+    #[allow(clippy::type_complexity)]
+    pub fn split_at<const K: usize>(
+        self,
+    ) -> (
+        ConsList<T, <[T; K] as ArrayToList<T>>::List>,
+        ConsList<T, <Ts as SplitAt<T, <[T; K] as ArrayToList<T>>::List>>::Suffix>,
+    )
+    where
+        [T; K]: ArrayToList<T>,
+        Ts: SplitAt<T, <[T; K] as ArrayToList<T>>::List>,
+    {
+        let (prefix, suffix) = self.list.split_at();
+        (
+            ConsList {
+                list: prefix,
+                marker: PhantomData,
+            },
+            ConsList {
+                list: suffix,
+                marker: PhantomData,
+            },
+        )
+    }
+
+    /// Move the elements into a `SmallVec`. No heap allocation is made if they fit in its inline capacity.
+    #[cfg(feature = "smallvec")]
+    pub fn into_smallvec<A: smallvec::Array<Item = T>>(self) -> smallvec::SmallVec<A> {
+        self.into_iter().collect()
+    }
+
+    /// Move the elements into an `ArrayVec`. Fails to compile if `CAP` is smaller than the length of the list.
+    #[cfg(feature = "arrayvec")]
+    pub fn into_arrayvec<const CAP: usize>(self) -> arrayvec::ArrayVec<T, CAP> {
+        const { assert!(Ts::LEN <= CAP, "list does not fit in ArrayVec capacity") };
+        self.into_iter().collect()
+    }
+
+    /// Convert the list into a `GenericArray` without copying elements one at a time. Fails to compile if `N` is not
+    /// the length of the list.
+    #[cfg(feature = "generic-array")]
+    pub fn into_generic_array<N: generic_array::ArrayLength>(
+        self,
+    ) -> generic_array::GenericArray<T, N> {
+        const {
+            assert!(
+                N::USIZE == Ts::LEN,
+                "GenericArray length must match list length"
+            );
+            assert_array_layout::<T, Ts>();
+        };
+        let list = ManuallyDrop::new(self.list);
+        // SAFETY: `GenericArray<T, N>` has the layout of `[T; N]`, as does the list, and the original list is wrapped
+        // in `ManuallyDrop` so each element is only dropped once.
+        unsafe { std::ptr::read((&raw const *list).cast::<generic_array::GenericArray<T, N>>()) }
+    }
+
+    /// Build a list from a `GenericArray` of the same length. Fails to compile if `N` is not the length of the list.
+    #[cfg(feature = "generic-array")]
+    pub fn from_generic_array<N: generic_array::ArrayLength>(
+        arr: generic_array::GenericArray<T, N>,
+    ) -> Self {
+        const {
+            assert!(
+                N::USIZE == Ts::LEN,
+                "GenericArray length must match list length"
+            );
+            assert_array_layout::<T, Ts>();
+        };
+        let arr = ManuallyDrop::new(arr);
+        // SAFETY: as in `into_generic_array`
+        let list = unsafe { std::ptr::read((&raw const *arr).cast::<Ts>()) };
+        ConsList {
+            list,
+            marker: PhantomData,
+        }
+    }
+
+    /// Concatenate two lists, keeping the combined length in the type
+    pub fn append<Us: ConsListT<T>>(self, other: ConsList<T, Us>) -> ConsList<T, Ts::Output>
+    where
+        Ts: Append<T, Us>,
+    {
+        ConsList {
+            list: self.list.append(other.list),
+            marker: PhantomData,
+        }
+    }
+}
+
+macro_rules! impl_from_array {
+    ($ts:ty;) => {};
+    ($ts:ty; $n:literal $($rest:literal)*) => {
+        impl<T> ArrayToList<T> for [T; $n] {
+            type List = $ts;
+        }
+
+        impl<T> From<[T; $n]> for ConsList<T, $ts> {
+            fn from(arr: [T; $n]) -> Self {
+                Self::from_array(arr)
+            }
+        }
+
+        impl_from_array!(Cons<T, $ts>; $($rest)*);
+    };
+}
+
+impl_from_array!(
+    Nil<T>; 0 1 2 3 4 5 6 7 8 9 10 11 12 13 14 15 16 17 18 19 20 21 22 23 24 25 26 27 28 29 30 31 32
+);
+
+impl<T, Ts: ConsListT<T> + Clone> Clone for ConsList<T, Ts> {
+    fn clone(&self) -> Self {
+        ConsList {
+            list: self.list.clone(),
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<T, Ts: ConsListT<T> + Copy> Copy for ConsList<T, Ts> {}
+
+impl<T, Ts: ConsListT<T> + Default> Default for ConsList<T, Ts> {
+    fn default() -> Self {
+        ConsList {
+            list: Ts::default(),
+            marker: PhantomData,
         }
     }
 }
 
+impl<T: fmt::Debug, Ts: ConsListT<T>> fmt::Debug for ConsList<T, Ts> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T: PartialEq, Ts: ConsListT<T>> PartialEq for ConsList<T, Ts> {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_slice() == other.as_slice()
+    }
+}
+
+impl<T: Eq, Ts: ConsListT<T>> Eq for ConsList<T, Ts> {}
+
+impl<T: Hash, Ts: ConsListT<T>> Hash for ConsList<T, Ts> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_slice().hash(state)
+    }
+}
+
+/// Build a [ConsList] from a list of elements, or from a single element repeated `N` times with `cons_list![elem; N]`.
+/// As with `vec!`, the repeated element must implement [Clone].
+///
+/// The list form expands to nested [ConsList::cons] calls, so it can build a list in a `const` or `static`:
+///
+/// ```
+/// use frunk_utils::{
+///     cons_list,
+///     cons_list::{Cons, Nil},
+///     ConsList,
+/// };
+///
+/// const PRIMES: ConsList<u32, Cons<u32, Cons<u32, Cons<u32, Nil<u32>>>>> = cons_list![2, 3, 5];
+/// assert_eq!(PRIMES.as_slice(), &[2, 3, 5]);
+/// ```
+#[macro_export]
+macro_rules! cons_list {
+    () => {
+        $crate::ConsList::nil()
+    };
+    ($elem:expr; $n:expr) => {{
+        let elem = $elem;
+        $crate::ConsList::from(::core::array::from_fn::<_, $n, _>(|_| {
+            ::core::clone::Clone::clone(&elem)
+        }))
+    }};
+    ($head:expr $(, $tail:expr)* $(,)?) => {
+        $crate::ConsList::cons($head, $crate::cons_list!($($tail),*))
+    };
+}
+
 impl<T, Ts: ConsListT<T>> IntoIterator for ConsList<T, Ts> {
     type Item = T;
     type IntoIter = Iter<T, Ts>;
@@ -72,6 +539,37 @@ impl<T, Ts: ConsListT<T>> IntoIterator for ConsList<T, Ts> {
     }
 }
 
+impl<'a, T, Ts: ConsListT<T>> IntoIterator for &'a ConsList<T, Ts> {
+    type Item = &'a T;
+    type IntoIter = std::slice::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T, Ts: ConsListT<T>> IntoIterator for &'a mut ConsList<T, Ts> {
+    type Item = &'a mut T;
+    type IntoIter = std::slice::IterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+/// The elements written so far into the front of a list under construction, dropped if it is abandoned part way
+struct Written<U> {
+    start: *mut U,
+    len: usize,
+}
+
+impl<U> Drop for Written<U> {
+    fn drop(&mut self) {
+        // SAFETY: The first `len` elements from `start` were written and nothing else owns them.
+        unsafe { std::ptr::drop_in_place(std::ptr::slice_from_raw_parts_mut(self.start, self.len)) }
+    }
+}
+
 pub struct Iter<T, Ts: ConsListT<T>> {
     list: ManuallyDrop<Ts>,
     alive: Range<usize>,
@@ -86,6 +584,17 @@ impl<T, Ts: ConsListT<T>> Iter<T, Ts> {
             marker: PhantomData,
         }
     }
+
+    /// Drop the elements in `dead`, which must already have been removed from `alive`
+    fn drop_range(&mut self, dead: Range<usize>) {
+        const { assert_array_layout::<T, Ts>() };
+        // SAFETY: The list is laid out like `[T; Ts::LEN]`, and the elements in `dead` are still initialized but no
+        // longer reachable through `alive`, so they are dropped exactly once.
+        unsafe {
+            let start = (&raw mut *self.list).cast::<T>().add(dead.start);
+            std::ptr::drop_in_place(std::ptr::slice_from_raw_parts_mut(start, dead.len()));
+        }
+    }
 }
 
 impl<T, Ts: ConsListT<T>> Iterator for Iter<T, Ts> {
@@ -105,6 +614,26 @@ impl<T, Ts: ConsListT<T>> Iterator for Iter<T, Ts> {
     fn count(self) -> usize {
         self.len()
     }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        let skipped = self.alive.start..self.alive.end.min(self.alive.start.saturating_add(n));
+        self.alive.start = skipped.end;
+        self.drop_range(skipped);
+        self.next()
+    }
+
+    fn last(mut self) -> Option<Self::Item> {
+        self.next_back()
+    }
+
+    fn fold<B, F: FnMut(B, Self::Item) -> B>(mut self, init: B, mut f: F) -> B {
+        let mut acc = init;
+        // `alive` is advanced before each call so that if `f` panics, only the remaining elements are dropped
+        for idx in self.alive.by_ref() {
+            acc = f(acc, unsafe { self.list.take_unchecked(idx) });
+        }
+        acc
+    }
 }
 
 impl<T, Ts: ConsListT<T>> DoubleEndedIterator for Iter<T, Ts> {
@@ -113,6 +642,21 @@ impl<T, Ts: ConsListT<T>> DoubleEndedIterator for Iter<T, Ts> {
             .next_back()
             .map(|idx| unsafe { self.list.take_unchecked(idx) })
     }
+
+    fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+        let skipped = self.alive.end.saturating_sub(n).max(self.alive.start)..self.alive.end;
+        self.alive.end = skipped.start;
+        self.drop_range(skipped);
+        self.next_back()
+    }
+
+    fn rfold<B, F: FnMut(B, Self::Item) -> B>(mut self, init: B, mut f: F) -> B {
+        let mut acc = init;
+        while let Some(idx) = self.alive.next_back() {
+            acc = f(acc, unsafe { self.list.take_unchecked(idx) });
+        }
+        acc
+    }
 }
 
 impl<T, Ts: ConsListT<T>> ExactSizeIterator for Iter<T, Ts> {
@@ -149,6 +693,233 @@ mod tests {
         drop(iter);
     }
 
+    #[test]
+    fn skipping_and_folding() {
+        let strings = || {
+            ConsList::from_array([1, 2, 3, 4, 5].map(|i: i32| i.to_string()))
+                as ConsList<_, <[String; 5] as ArrayToList<_>>::List>
+        };
+        let mut iter = strings().into_iter();
+        assert_eq!(iter.nth(1).as_deref(), Some("2"));
+        assert_eq!(iter.nth_back(1).as_deref(), Some("4"));
+        assert_eq!(iter.len(), 1);
+        assert_eq!(iter.nth(1), None);
+        assert_eq!(iter.len(), 0);
+
+        assert_eq!(strings().into_iter().last().as_deref(), Some("5"));
+        assert_eq!(
+            strings().into_iter().fold(String::new(), |acc, s| acc + &s),
+            "12345"
+        );
+        assert_eq!(
+            strings()
+                .into_iter()
+                .rfold(String::new(), |acc, s| acc + &s),
+            "54321"
+        );
+        let mut iter = strings().into_iter();
+        iter.next();
+        assert_eq!(iter.fold(0, |n, _| n + 1), 4);
+    }
+
+    #[test]
+    fn into_array() {
+        let list = ConsList::cons(1, ConsList::cons(2, ConsList::cons(3, ConsList::nil())));
+        assert_eq!(list.into_array(), [1, 2, 3]);
+        let list = ConsList::cons(
+            String::from("a"),
+            ConsList::cons(String::from("b"), ConsList::nil()),
+        );
+        assert_eq!(list.into_array(), ["a", "b"]);
+        let empty: [u8; 0] = ConsList::nil().into_array();
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    fn into_vec() {
+        let list = ConsList::cons(
+            String::from("a"),
+            ConsList::cons(String::from("b"), ConsList::nil()),
+        );
+        assert_eq!(list.into_vec(), vec!["a", "b"]);
+        let list = ConsList::cons(1, ConsList::cons(2, ConsList::nil()));
+        assert_eq!(&*list.into_boxed_slice(), &[1, 2]);
+    }
+
+    #[test]
+    fn borrowing_iter() {
+        let mut list = ConsList::cons(1, ConsList::cons(2, ConsList::cons(3, ConsList::nil())));
+        for x in list.iter_mut() {
+            *x *= 10;
+        }
+        assert_eq!(
+            list.iter().rev().copied().collect::<Vec<_>>(),
+            vec![30, 20, 10]
+        );
+        assert_eq!(list.iter().len(), 3);
+        assert_eq!(list.as_slice(), &[10, 20, 30]);
+        assert!(ConsList::<u8, _>::nil().iter().next().is_none());
+    }
+
+    #[test]
+    fn standard_traits() {
+        let list = ConsList::cons(1, ConsList::cons(2, ConsList::nil()));
+        let copy = list;
+        assert_eq!(list, copy);
+        assert_ne!(list, ConsList::cons(1, ConsList::cons(3, ConsList::nil())));
+        assert_eq!(format!("{:?}", list), "[1, 2]");
+        let default: ConsList<String, Cons<String, Cons<String, Nil<String>>>> = Default::default();
+        assert_eq!(
+            default.clone().into_vec(),
+            vec![String::new(), String::new()]
+        );
+    }
+
+    #[test]
+    fn append() {
+        let left = ConsList::cons(1, ConsList::cons(2, ConsList::nil()));
+        let right = ConsList::cons(3, ConsList::nil());
+        let both = left.append(right);
+        assert_eq!(both.into_array(), [1, 2, 3]);
+        assert_eq!(ConsList::nil().append(right).as_slice(), &[3]);
+    }
+
+    #[test]
+    fn rev() {
+        let list = ConsList::cons(1, ConsList::cons(2, ConsList::cons(3, ConsList::nil())));
+        assert_eq!(list.rev().into_array(), [3, 2, 1]);
+        let mut list = ConsList::cons("a", ConsList::cons("b", ConsList::nil()));
+        list.reverse();
+        assert_eq!(list.as_slice(), &["b", "a"]);
+    }
+
+    #[test]
+    fn map() {
+        let list = ConsList::cons(1, ConsList::cons(2, ConsList::cons(3, ConsList::nil())));
+        let mut calls = Vec::new();
+        let mapped = list.map(|x| {
+            calls.push(x);
+            x.to_string()
+        });
+        assert_eq!(mapped.into_array(), ["1", "2", "3"]);
+        assert_eq!(calls, vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn map_panic_drops_each_element_once() {
+        use std::rc::Rc;
+
+        let counter = Rc::new(());
+        let list = ConsList::from([(); 4].map(|()| Rc::clone(&counter)));
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            let mut calls = 0;
+            list.map(|rc| {
+                calls += 1;
+                assert!(calls < 3, "stop part way");
+                rc
+            })
+        }));
+        assert!(result.is_err());
+        assert_eq!(Rc::strong_count(&counter), 1);
+    }
+
+    #[test]
+    fn from_array() {
+        let list = ConsList::from([String::from("a"), String::from("b")]);
+        assert_eq!(list.as_slice(), &["a", "b"]);
+        let list: ConsList<_, Cons<_, Cons<_, Nil<_>>>> = ConsList::from_array([1, 2]);
+        assert_eq!(list, ConsList::cons(1, ConsList::cons(2, ConsList::nil())));
+        assert_eq!(ConsList::from([0u8; 32]).as_slice().len(), 32);
+    }
+
+    #[test]
+    fn cons_list_macro() {
+        let list = cons_list![1, 2, 3];
+        assert_eq!(
+            list,
+            ConsList::cons(1, ConsList::cons(2, ConsList::cons(3, ConsList::nil())))
+        );
+        let list = cons_list![String::from("x"); 4];
+        assert_eq!(list.into_array(), ["x", "x", "x", "x"]);
+        let empty: ConsList<u8, _> = cons_list![];
+        assert!(empty.as_slice().is_empty());
+        assert_eq!(cons_list![0u8; 5].as_slice(), &[0; 5]);
+    }
+
+    #[test]
+    fn const_construction() {
+        const LIST: ConsList<u8, Cons<u8, Cons<u8, Nil<u8>>>> = cons_list![1, 2];
+        static EMPTY: ConsList<String, Nil<String>> = ConsList::nil();
+        assert_eq!(LIST.as_slice(), &[1, 2]);
+        assert!(EMPTY.as_slice().is_empty());
+    }
+
+    #[test]
+    fn push_pop() {
+        let list = cons_list![2, 3].push(1);
+        assert_eq!(list, cons_list![1, 2, 3]);
+        let (head, tail) = list.pop();
+        assert_eq!(head, 1);
+        assert_eq!(tail, cons_list![2, 3]);
+    }
+
+    #[test]
+    fn split_at() {
+        let list = cons_list![1, 2, 3, 4, 5];
+        let (prefix, suffix) = list.split_at::<2>();
+        assert_eq!(prefix.into_array(), [1, 2]);
+        assert_eq!(suffix.into_array(), [3, 4, 5]);
+        let (empty, all) = list.split_at::<0>();
+        assert!(empty.as_slice().is_empty());
+        assert_eq!(all, list);
+        let (all, empty) = list.split_at::<5>();
+        assert_eq!(all, list);
+        assert!(empty.as_slice().is_empty());
+    }
+
+    #[cfg(feature = "smallvec")]
+    #[test]
+    fn into_smallvec() {
+        let small = cons_list![1, 2, 3].into_smallvec::<[i32; 4]>();
+        assert!(!small.spilled());
+        assert_eq!(small.as_slice(), &[1, 2, 3]);
+    }
+
+    #[cfg(feature = "generic-array")]
+    #[test]
+    fn generic_array_round_trip() {
+        use generic_array::{typenum::U3, GenericArray};
+
+        let array: GenericArray<String, U3> =
+            cons_list!["a".to_string(), "b".to_string(), "c".to_string()].into_generic_array();
+        assert_eq!(array.as_slice(), ["a", "b", "c"]);
+        let list: ConsList<_, <[String; 3] as ArrayToList<_>>::List> =
+            ConsList::from_generic_array(array);
+        assert_eq!(list.into_vec(), ["a", "b", "c"]);
+    }
+
+    #[cfg(feature = "typenum")]
+    #[test]
+    fn typenum_len() {
+        use typenum::{Unsigned, U3};
+
+        fn len_of<T, Ts: ConsListLen<T>>(_: &ConsList<T, Ts>) -> PhantomData<Ts::Len> {
+            PhantomData
+        }
+
+        let list = cons_list![1, 2, 3];
+        let _: PhantomData<U3> = len_of(&list);
+        assert_eq!(<<Nil<u8> as ConsListLen<u8>>::Len as Unsigned>::USIZE, 0);
+    }
+
+    #[cfg(feature = "arrayvec")]
+    #[test]
+    fn into_arrayvec() {
+        let array = cons_list![1, 2, 3].into_arrayvec::<4>();
+        assert_eq!(array.as_slice(), &[1, 2, 3]);
+        assert_eq!(array.remaining_capacity(), 1);
+    }
+
     #[test]
     fn drop_behavior() {
         static NUM_ALLOC: AtomicIsize = AtomicIsize::new(0);
@@ -244,4 +1015,19 @@ mod tests {
         }
         assert_eq!(NUM_ALLOC.load(Ordering::SeqCst), 0);
     }
+
+    #[test]
+    fn over_aligned_elements() {
+        #[derive(Clone, Copy, Debug, PartialEq)]
+        #[repr(align(16))]
+        struct Aligned(u8);
+
+        let list: ConsList<_, <[Aligned; 3] as ArrayToList<_>>::List> =
+            ConsList::from_array([Aligned(1), Aligned(2), Aligned(3)]);
+        assert_eq!(list.as_slice(), [Aligned(1), Aligned(2), Aligned(3)]);
+        assert_eq!(list.into_vec(), [Aligned(1), Aligned(2), Aligned(3)]);
+
+        let units: ConsList<_, <[(); 2] as ArrayToList<_>>::List> = ConsList::from_array([(), ()]);
+        assert_eq!(units.into_iter().count(), 2);
+    }
 }
@@ -0,0 +1,113 @@
+use std::{fmt, marker::PhantomData, mem::MaybeUninit};
+
+use serde::{
+    de::{self, SeqAccess, Visitor},
+    ser::SerializeTuple,
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+
+use super::{ConsList, ConsListT};
+
+impl<T: Serialize, Ts: ConsListT<T>> Serialize for ConsList<T, Ts> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut tuple = serializer.serialize_tuple(Ts::LEN)?;
+        for elem in self {
+            tuple.serialize_element(elem)?;
+        }
+        tuple.end()
+    }
+}
+
+impl<'de, T: Deserialize<'de>, Ts: ConsListT<T>> Deserialize<'de> for ConsList<T, Ts> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_tuple(Ts::LEN, ConsListVisitor(PhantomData))
+    }
+}
+
+struct ConsListVisitor<T, Ts>(PhantomData<(T, Ts)>);
+
+impl<'de, T: Deserialize<'de>, Ts: ConsListT<T>> Visitor<'de> for ConsListVisitor<T, Ts> {
+    type Value = ConsList<T, Ts>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "a sequence of length {}", Ts::LEN)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut partial = PartialList::<T, Ts>::new();
+        while partial.len < Ts::LEN {
+            match seq.next_element()? {
+                Some(elem) => partial.push(elem),
+                None => return Err(de::Error::invalid_length(partial.len, &self)),
+            }
+        }
+        Ok(ConsList {
+            list: partial.finish(),
+            marker: PhantomData,
+        })
+    }
+}
+
+/// A list whose first `len` elements have been initialized. Those elements are dropped if deserialization fails
+/// partway through.
+struct PartialList<T, Ts> {
+    list: MaybeUninit<Ts>,
+    len: usize,
+    marker: PhantomData<T>,
+}
+
+impl<T, Ts: ConsListT<T>> PartialList<T, Ts> {
+    fn new() -> Self {
+        const { super::assert_array_layout::<T, Ts>() };
+        PartialList {
+            list: MaybeUninit::uninit(),
+            len: 0,
+            marker: PhantomData,
+        }
+    }
+
+    fn push(&mut self, elem: T) {
+        assert!(self.len < Ts::LEN, "Index out of bounds");
+        // SAFETY: The list is laid out like `[T; Ts::LEN]` and `len` is in bounds.
+        unsafe { self.list.as_mut_ptr().cast::<T>().add(self.len).write(elem) };
+        self.len += 1;
+    }
+
+    fn finish(mut self) -> Ts {
+        assert_eq!(self.len, Ts::LEN, "list not fully initialized");
+        self.len = 0;
+        // SAFETY: Every element has been written, and resetting `len` keeps `Drop` from touching them.
+        unsafe { self.list.as_ptr().read() }
+    }
+}
+
+impl<T, Ts> Drop for PartialList<T, Ts> {
+    fn drop(&mut self) {
+        let initialized =
+            std::ptr::slice_from_raw_parts_mut(self.list.as_mut_ptr().cast::<T>(), self.len);
+        // SAFETY: Exactly the first `len` elements have been initialized.
+        unsafe { std::ptr::drop_in_place(initialized) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cons_list::{Cons, Nil};
+
+    #[test]
+    fn round_trip() {
+        let list = ConsList::cons(1, ConsList::cons(2, ConsList::cons(3, ConsList::nil())));
+        let json = serde_json::to_string(&list).unwrap();
+        assert_eq!(json, "[1,2,3]");
+        let back: ConsList<i32, _> = serde_json::from_str(&json).unwrap();
+        assert_eq!(back, list);
+    }
+
+    #[test]
+    fn wrong_length() {
+        type List = ConsList<String, Cons<String, Cons<String, Nil<String>>>>;
+        assert!(serde_json::from_str::<List>(r#"["a"]"#).is_err());
+        assert!(serde_json::from_str::<List>(r#"["a", "b", "c"]"#).is_err());
+    }
+}
@@ -0,0 +1,207 @@
+//! Enum counterparts to the struct traversals, for enums whose [Generic] representation is a [Coproduct] with one HList
+//! of fields per variant. frunk's derive only handles structs, so such impls are written by hand. A [Func] used here is
+//! called with the HList of fields of whichever variant is active.
+
+use frunk::{
+    coproduct::{CoproductFoldable, CoproductMappable},
+    Coproduct, Generic,
+};
+
+use crate::{Func, Poly};
+
+impl<F: Func<Head>, Head, Tail: CoproductMappable<Poly<F>>> CoproductMappable<Poly<F>>
+    for Coproduct<Head, Tail>
+{
+    type Output =
+        Coproduct<<F as Func<Head>>::Output, <Tail as CoproductMappable<Poly<F>>>::Output>;
+
+    fn map(self, mut mapper: Poly<F>) -> Self::Output {
+        match self {
+            Coproduct::Inl(head) => Coproduct::Inl(mapper.0.call(head)),
+            Coproduct::Inr(tail) => Coproduct::Inr(tail.map(mapper)),
+        }
+    }
+}
+
+impl<F: Func<Head, Output = R>, R, Head, Tail: CoproductFoldable<Poly<F>, R>>
+    CoproductFoldable<Poly<F>, R> for Coproduct<Head, Tail>
+{
+    fn fold(self, mut folder: Poly<F>) -> R {
+        match self {
+            Coproduct::Inl(head) => folder.0.call(head),
+            Coproduct::Inr(tail) => tail.fold(folder),
+        }
+    }
+}
+
+/// Convenience functions for mapping and folding over the active variant of an enum implementing [Generic] without
+/// having to explicitly call [Generic::from] and [Generic::into]
+pub trait WithCoproductGeneric: Generic {
+    /// Map the fields of the active variant into the corresponding variant of a similarly-shaped enum
+    fn map_variant<U: Generic, F>(self, f: F) -> U
+    where
+        Self::Repr: CoproductMappable<Poly<F>, Output = U::Repr>;
+
+    /// Reduce the active variant to a single value. `f` must produce the same output type for every variant.
+    fn fold_variant<R, F>(self, f: F) -> R
+    where
+        Self::Repr: CoproductFoldable<Poly<F>, R>;
+}
+
+impl<T: Generic> WithCoproductGeneric for T {
+    fn map_variant<U: Generic, F>(self, f: F) -> U
+    where
+        Self::Repr: CoproductMappable<Poly<F>, Output = U::Repr>,
+    {
+        Generic::from(Generic::into(self).map(Poly(f)))
+    }
+
+    fn fold_variant<R, F>(self, f: F) -> R
+    where
+        Self::Repr: CoproductFoldable<Poly<F>, R>,
+    {
+        Generic::into(self).fold(Poly(f))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use frunk::{hlist::HMappable, Coprod, HCons, HList, HNil};
+
+    use super::*;
+
+    enum Event {
+        Click { x: i32, y: i32 },
+        Key(char),
+        Quit,
+    }
+
+    impl Generic for Event {
+        type Repr = Coprod!(HList!(i32, i32), HList!(char), HNil);
+
+        fn into(self) -> Self::Repr {
+            match self {
+                Event::Click { x, y } => Coproduct::inject(frunk::hlist![x, y]),
+                Event::Key(c) => Coproduct::inject(frunk::hlist![c]),
+                Event::Quit => Coproduct::inject(HNil),
+            }
+        }
+
+        fn from(repr: Self::Repr) -> Self {
+            repr.fold(frunk::hlist![
+                |frunk::hlist_pat![x, y]| Event::Click { x, y },
+                |frunk::hlist_pat![c]| Event::Key(c),
+                |HNil| Event::Quit,
+            ])
+        }
+    }
+
+    #[derive(Debug, PartialEq)]
+    enum Scaled {
+        Click { x: i64, y: i64 },
+        Key(char),
+        Quit,
+    }
+
+    impl Generic for Scaled {
+        type Repr = Coprod!(HList!(i64, i64), HList!(char), HNil);
+
+        fn into(self) -> Self::Repr {
+            match self {
+                Scaled::Click { x, y } => Coproduct::inject(frunk::hlist![x, y]),
+                Scaled::Key(c) => Coproduct::inject(frunk::hlist![c]),
+                Scaled::Quit => Coproduct::inject(HNil),
+            }
+        }
+
+        fn from(repr: Self::Repr) -> Self {
+            repr.fold(frunk::hlist![
+                |frunk::hlist_pat![x, y]| Scaled::Click { x, y },
+                |frunk::hlist_pat![c]| Scaled::Key(c),
+                |HNil| Scaled::Quit,
+            ])
+        }
+    }
+
+    struct Scale(i64);
+
+    impl Func<i32> for Scale {
+        type Output = i64;
+
+        fn call(&mut self, i: i32) -> i64 {
+            i64::from(i) * self.0
+        }
+    }
+
+    impl Func<char> for Scale {
+        type Output = char;
+
+        fn call(&mut self, i: char) -> char {
+            i
+        }
+    }
+
+    struct ScaleVariant(i64);
+
+    impl<H: HMappable<Poly<Scale>>> Func<H> for ScaleVariant {
+        type Output = H::Output;
+
+        fn call(&mut self, i: H) -> H::Output {
+            i.map(Poly(Scale(self.0)))
+        }
+    }
+
+    #[test]
+    fn map_variant() {
+        let scaled: Scaled = Event::Click { x: 1, y: -2 }.map_variant(ScaleVariant(10));
+        assert_eq!(scaled, Scaled::Click { x: 10, y: -20 });
+        let scaled: Scaled = Event::Key('q').map_variant(ScaleVariant(10));
+        assert_eq!(scaled, Scaled::Key('q'));
+        let scaled: Scaled = Event::Quit.map_variant(ScaleVariant(10));
+        assert_eq!(scaled, Scaled::Quit);
+    }
+
+    struct Describe {
+        seen: usize,
+    }
+
+    impl Func<HCons<i32, HCons<i32, HNil>>> for Describe {
+        type Output = String;
+
+        fn call(&mut self, i: HCons<i32, HCons<i32, HNil>>) -> String {
+            self.seen += 1;
+            let (x, y) = i.into_tuple2();
+            format!("click at ({x}, {y})")
+        }
+    }
+
+    impl Func<HCons<char, HNil>> for Describe {
+        type Output = String;
+
+        fn call(&mut self, i: HCons<char, HNil>) -> String {
+            self.seen += 1;
+            format!("key {}", i.head)
+        }
+    }
+
+    impl Func<HNil> for Describe {
+        type Output = String;
+
+        fn call(&mut self, _i: HNil) -> String {
+            self.seen += 1;
+            "quit".to_string()
+        }
+    }
+
+    #[test]
+    fn fold_variant() {
+        let mut describe = Describe { seen: 0 };
+        let events = [Event::Click { x: 3, y: 4 }, Event::Key('a'), Event::Quit];
+        let descriptions = events
+            .into_iter()
+            .map(|e| e.fold_variant(&mut describe))
+            .collect::<Vec<String>>();
+        assert_eq!(descriptions, ["click at (3, 4)", "key a", "quit"]);
+        assert_eq!(describe.seen, 3);
+    }
+}
@@ -0,0 +1,304 @@
+//! Mapping [StringRecord]s into structs with a per-field [TryFunc], as a lightweight alternative to serde when the
+//! conversion differs from field to field. Structs implementing [Generic](frunk::Generic) are filled by column
+//! position; structs implementing [LabelledGeneric](frunk::LabelledGeneric) can instead be filled by matching field
+//! names against the header row.
+
+use std::{error::Error, fmt, marker::PhantomData, str::FromStr};
+
+use ::csv::StringRecord;
+use frunk::{
+    labelled::{field_with_name, Field},
+    prelude::HList,
+    HCons, HNil,
+};
+
+use crate::{field_names::Label, TryFunc};
+
+/// One cell of a record, passed to the conversion [TryFunc] of the field of type `T` it's destined for
+pub struct Cell<'r, T> {
+    pub value: &'r str,
+    /// The column index within the record
+    pub index: usize,
+    /// The name of the field, when filling a labelled struct
+    pub field: Option<&'static str>,
+    marker: PhantomData<fn() -> T>,
+}
+
+impl<'r, T> Cell<'r, T> {
+    fn new(value: &'r str, index: usize, field: Option<&'static str>) -> Self {
+        Cell {
+            value,
+            index,
+            field,
+            marker: PhantomData,
+        }
+    }
+}
+
+/// A [TryFunc] converting each cell with the [FromStr] impl of the field's type
+pub struct ParseCell;
+
+impl<T: FromStr> TryFunc<Cell<'_, T>> for ParseCell
+where
+    T::Err: Error + Send + Sync + 'static,
+{
+    type Output = T;
+    type Error = Box<dyn Error + Send + Sync>;
+
+    fn try_call(&mut self, i: Cell<'_, T>) -> Result<Self::Output, Self::Error> {
+        Ok(i.value.parse()?)
+    }
+}
+
+/// Returned when a record can't be mapped into a struct
+#[derive(Debug)]
+pub enum RecordError<E> {
+    /// The record doesn't have the expected number of columns
+    Length { expected: usize, found: usize },
+    /// No header matches the name of a field
+    MissingColumn(&'static str),
+    /// The conversion failed for the cell at `index`
+    Convert {
+        index: usize,
+        field: Option<&'static str>,
+        source: E,
+    },
+}
+
+impl<E: fmt::Display> fmt::Display for RecordError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RecordError::Length { expected, found } => {
+                write!(f, "expected {expected} columns but found {found}")
+            }
+            RecordError::MissingColumn(field) => write!(f, "no column for field `{field}`"),
+            RecordError::Convert {
+                index,
+                field: Some(field),
+                source,
+            } => write!(
+                f,
+                "invalid value for field `{field}` in column {index}: {source}"
+            ),
+            RecordError::Convert {
+                index,
+                field: None,
+                source,
+            } => write!(f, "invalid value in column {index}: {source}"),
+        }
+    }
+}
+
+impl<E: Error + 'static> Error for RecordError<E> {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            RecordError::Convert { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+/// Fill an HList from the columns of a record by position
+pub trait FromRecord<F, E>: HList {
+    /// Convert the cells starting at column `index`, one per element, stopping at the first failure
+    fn from_record(record: &StringRecord, index: usize, f: F) -> Result<Self, RecordError<E>>;
+}
+
+impl<F, E> FromRecord<F, E> for HNil {
+    fn from_record(_record: &StringRecord, _index: usize, _f: F) -> Result<Self, RecordError<E>> {
+        Ok(HNil)
+    }
+}
+
+impl<F: for<'r> TryFunc<Cell<'r, T>, Output = T, Error = E>, E, T, Tail: FromRecord<F, E>>
+    FromRecord<F, E> for HCons<T, Tail>
+{
+    fn from_record(record: &StringRecord, index: usize, mut f: F) -> Result<Self, RecordError<E>> {
+        let value = record.get(index).ok_or(RecordError::Length {
+            expected: Self::LEN + index,
+            found: record.len(),
+        })?;
+        let head = f
+            .try_call(Cell::new(value, index, None))
+            .map_err(|source| RecordError::Convert {
+                index,
+                field: None,
+                source,
+            })?;
+        Ok(HCons {
+            head,
+            tail: Tail::from_record(record, index + 1, f)?,
+        })
+    }
+}
+
+/// Fill a labelled HList from the columns of a record whose header matches each field's name
+pub trait FromRecordNamed<F, E>: HList {
+    /// Convert the cell for each field, stopping at the first failure. Columns that don't match a field are ignored.
+    fn from_record_named(
+        headers: &StringRecord,
+        record: &StringRecord,
+        f: F,
+    ) -> Result<Self, RecordError<E>>;
+}
+
+impl<F, E> FromRecordNamed<F, E> for HNil {
+    fn from_record_named(
+        _headers: &StringRecord,
+        _record: &StringRecord,
+        _f: F,
+    ) -> Result<Self, RecordError<E>> {
+        Ok(HNil)
+    }
+}
+
+impl<
+        F: for<'r> TryFunc<Cell<'r, T>, Output = T, Error = E>,
+        E,
+        Name: Label,
+        T,
+        Tail: FromRecordNamed<F, E>,
+    > FromRecordNamed<F, E> for HCons<Field<Name, T>, Tail>
+{
+    fn from_record_named(
+        headers: &StringRecord,
+        record: &StringRecord,
+        mut f: F,
+    ) -> Result<Self, RecordError<E>> {
+        let field = Name::NAME;
+        let index = headers
+            .iter()
+            .position(|header| header == field)
+            .ok_or(RecordError::MissingColumn(field))?;
+        let value = record.get(index).ok_or(RecordError::Length {
+            expected: headers.len(),
+            found: record.len(),
+        })?;
+        let value = f
+            .try_call(Cell::new(value, index, Some(field)))
+            .map_err(|source| RecordError::Convert {
+                index,
+                field: Some(field),
+                source,
+            })?;
+        Ok(HCons {
+            head: field_with_name(field, value),
+            tail: Tail::from_record_named(headers, record, f)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use frunk::{Generic, LabelledGeneric};
+
+    use super::*;
+    use crate::{WithGeneric, WithLabelledGeneric};
+
+    #[derive(Generic, LabelledGeneric, Debug, PartialEq)]
+    struct Reading {
+        sensor: String,
+        celsius: f64,
+        ok: bool,
+    }
+
+    /// Trims every cell before converting it and spells booleans "yes"/"no"
+    struct Lenient;
+
+    impl TryFunc<Cell<'_, String>> for Lenient {
+        type Output = String;
+        type Error = String;
+
+        fn try_call(&mut self, i: Cell<'_, String>) -> Result<String, String> {
+            Ok(i.value.trim().to_string())
+        }
+    }
+
+    impl TryFunc<Cell<'_, f64>> for Lenient {
+        type Output = f64;
+        type Error = String;
+
+        fn try_call(&mut self, i: Cell<'_, f64>) -> Result<f64, String> {
+            i.value
+                .trim()
+                .parse()
+                .map_err(|_| format!("bad number {:?}", i.value))
+        }
+    }
+
+    impl TryFunc<Cell<'_, bool>> for Lenient {
+        type Output = bool;
+        type Error = String;
+
+        fn try_call(&mut self, i: Cell<'_, bool>) -> Result<bool, String> {
+            match i.value.trim() {
+                "yes" => Ok(true),
+                "no" => Ok(false),
+                other => Err(format!("bad flag {other:?}")),
+            }
+        }
+    }
+
+    #[test]
+    fn by_position() {
+        let record = StringRecord::from(vec!["a1", "21.5", "true"]);
+        let reading: Reading = WithGeneric::from_csv_record(&record, ParseCell).unwrap();
+        assert_eq!(
+            reading,
+            Reading {
+                sensor: "a1".to_string(),
+                celsius: 21.5,
+                ok: true,
+            }
+        );
+        let short = StringRecord::from(vec!["a1", "21.5"]);
+        let err = <Reading as WithGeneric>::from_csv_record(&short, ParseCell).unwrap_err();
+        assert!(matches!(
+            err,
+            RecordError::Length {
+                expected: 3,
+                found: 2
+            }
+        ));
+    }
+
+    #[test]
+    fn by_header() {
+        let mut reader = ::csv::ReaderBuilder::new()
+            .from_reader("ok,unit,sensor,celsius\nyes,C,b2, 19.0\nmaybe,C,b3,18\n".as_bytes());
+        let headers = reader.headers().unwrap().clone();
+        let mut records = reader.records().map(Result::unwrap);
+
+        let reading: Reading =
+            WithLabelledGeneric::from_csv_record_named(&headers, &records.next().unwrap(), Lenient)
+                .unwrap();
+        assert_eq!(
+            reading,
+            Reading {
+                sensor: "b2".to_string(),
+                celsius: 19.0,
+                ok: true,
+            }
+        );
+
+        let err = <Reading as WithLabelledGeneric>::from_csv_record_named(
+            &headers,
+            &records.next().unwrap(),
+            Lenient,
+        )
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "invalid value for field `ok` in column 0: bad flag \"maybe\""
+        );
+
+        let missing = StringRecord::from(vec!["sensor", "ok"]);
+        let err = <Reading as WithLabelledGeneric>::from_csv_record_named(
+            &missing,
+            &StringRecord::from(vec!["c4", "no"]),
+            Lenient,
+        )
+        .unwrap_err();
+        assert!(matches!(err, RecordError::MissingColumn("celsius")));
+    }
+}
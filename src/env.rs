@@ -0,0 +1,183 @@
+//! Loading a labelled struct from environment variables named after its fields, parsing each one with its [FromStr]
+//! impl. Every field is attempted so that all of the missing or invalid variables are reported together.
+
+use std::{env::VarError, error::Error, fmt, str::FromStr};
+
+use frunk::{
+    labelled::{field_with_name, Field},
+    prelude::HList,
+    HCons, HNil,
+};
+
+use crate::field_names::Label;
+
+/// Why a single environment variable couldn't be used
+#[derive(Debug)]
+pub enum EnvVarError {
+    Missing,
+    NotUnicode,
+    Invalid(Box<dyn Error + Send + Sync>),
+}
+
+impl fmt::Display for EnvVarError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EnvVarError::Missing => write!(f, "not set"),
+            EnvVarError::NotUnicode => write!(f, "not valid unicode"),
+            EnvVarError::Invalid(err) => write!(f, "invalid: {err}"),
+        }
+    }
+}
+
+/// Every variable that couldn't be used, in field declaration order, along with the reason
+#[derive(Debug)]
+pub struct EnvError(pub Vec<(String, EnvVarError)>);
+
+impl fmt::Display for EnvError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "failed to load configuration from the environment")?;
+        for (var, err) in &self.0 {
+            write!(f, "\n  {var}: {err}")?;
+        }
+        Ok(())
+    }
+}
+
+impl Error for EnvError {}
+
+/// The variable a field is read from: `PREFIX_FIELD` with the field name in upper case, or just `FIELD` if the prefix
+/// is empty
+pub fn var_name(prefix: &str, field: &str) -> String {
+    let field = field.to_uppercase();
+    if prefix.is_empty() {
+        field
+    } else {
+        format!("{prefix}_{field}")
+    }
+}
+
+pub trait FromEnv: HList {
+    /// Look up and parse the variable for every field. Failures are pushed onto `errors` and the HList is only
+    /// produced if every field succeeded.
+    fn from_env(
+        prefix: &str,
+        lookup: &mut impl FnMut(&str) -> Result<String, VarError>,
+        errors: &mut Vec<(String, EnvVarError)>,
+    ) -> Option<Self>;
+}
+
+impl FromEnv for HNil {
+    fn from_env(
+        _prefix: &str,
+        _lookup: &mut impl FnMut(&str) -> Result<String, VarError>,
+        _errors: &mut Vec<(String, EnvVarError)>,
+    ) -> Option<Self> {
+        Some(HNil)
+    }
+}
+
+impl<Name: Label, T: FromStr, Tail: FromEnv> FromEnv for HCons<Field<Name, T>, Tail>
+where
+    T::Err: Error + Send + Sync + 'static,
+{
+    fn from_env(
+        prefix: &str,
+        lookup: &mut impl FnMut(&str) -> Result<String, VarError>,
+        errors: &mut Vec<(String, EnvVarError)>,
+    ) -> Option<Self> {
+        let var = var_name(prefix, Name::NAME);
+        let head = match lookup(&var) {
+            Ok(value) => value
+                .parse()
+                .map_err(|err: T::Err| EnvVarError::Invalid(Box::new(err))),
+            Err(VarError::NotPresent) => Err(EnvVarError::Missing),
+            Err(VarError::NotUnicode(_)) => Err(EnvVarError::NotUnicode),
+        };
+        let head = match head {
+            Ok(value) => Some(field_with_name(Name::NAME, value)),
+            Err(err) => {
+                errors.push((var, err));
+                None
+            }
+        };
+        let tail = Tail::from_env(prefix, lookup, errors);
+        Some(HCons {
+            head: head?,
+            tail: tail?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use frunk::LabelledGeneric;
+
+    use super::*;
+    use crate::WithLabelledGeneric;
+
+    #[derive(LabelledGeneric, Debug, PartialEq)]
+    struct ServiceConfig {
+        listen_port: u16,
+        database_url: String,
+        workers: usize,
+    }
+
+    /// Looks variables up in a map rather than the process environment, which is shared between tests
+    fn lookup(vars: &[(&str, &str)]) -> impl FnMut(&str) -> Result<String, VarError> {
+        let vars: HashMap<String, String> = vars
+            .iter()
+            .map(|&(var, value)| (var.to_string(), value.to_string()))
+            .collect();
+        move |var| vars.get(var).cloned().ok_or(VarError::NotPresent)
+    }
+
+    #[test]
+    fn from_env() {
+        let vars = lookup(&[
+            ("APP_LISTEN_PORT", "8080"),
+            ("APP_DATABASE_URL", "postgres://db"),
+            ("APP_WORKERS", "4"),
+        ]);
+        let config = ServiceConfig::from_env_with("APP", vars).unwrap();
+        assert_eq!(
+            config,
+            ServiceConfig {
+                listen_port: 8080,
+                database_url: "postgres://db".to_string(),
+                workers: 4,
+            }
+        );
+    }
+
+    #[test]
+    fn from_env_reports_every_failure() {
+        let vars = lookup(&[
+            ("APP_LISTEN_PORT", "http"),
+            ("APP_DATABASE_URL", "postgres://db"),
+        ]);
+        let EnvError(errors) = ServiceConfig::from_env_with("APP", vars).unwrap_err();
+        let vars: Vec<_> = errors.iter().map(|(var, _)| var.as_str()).collect();
+        assert_eq!(vars, ["APP_LISTEN_PORT", "APP_WORKERS"]);
+        assert!(matches!(errors[0].1, EnvVarError::Invalid(_)));
+        assert!(matches!(errors[1].1, EnvVarError::Missing));
+    }
+
+    #[test]
+    fn from_env_without_prefix() {
+        let vars = lookup(&[
+            ("LISTEN_PORT", "1"),
+            ("DATABASE_URL", "x"),
+            ("WORKERS", "2"),
+        ]);
+        assert_eq!(
+            ServiceConfig::from_env_with("", vars).unwrap(),
+            ServiceConfig {
+                listen_port: 1,
+                database_url: "x".to_string(),
+                workers: 2,
+            }
+        );
+    }
+}
@@ -0,0 +1,197 @@
+//! Field names of [LabelledGeneric](frunk::LabelledGeneric) structs, recovered at compile time from the type-level
+//! labels that frunk's derive attaches to each field.
+
+use std::marker::PhantomData;
+
+use frunk::{
+    labelled::{chars, Field},
+    prelude::HList,
+    HCons, HNil,
+};
+
+use crate::cons_list::{Cons, ConsList, ConsListT, Nil};
+
+/// The longest field name, in bytes, that can be recovered from a label
+pub const MAX_NAME_LEN: usize = 256;
+
+/// A single character of a type-level label. Escapes for non-identifier characters are represented by the marker bytes
+/// [UNICODE_START] and [UNICODE_END].
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` is not a field label of at most 64 characters",
+    note = "field names are only recovered from labels of up to 64 characters; consider shortening the field name"
+)]
+pub trait LabelChar {
+    const BYTE: u8;
+}
+
+pub const UNICODE_START: u8 = 0x01;
+pub const UNICODE_END: u8 = 0x02;
+
+macro_rules! impl_label_char {
+    ($($c:ident => $byte:expr),* $(,)?) => {
+        $(
+            impl LabelChar for chars::$c {
+                const BYTE: u8 = $byte;
+            }
+        )*
+    };
+}
+
+macro_rules! impl_label_chars_as_is {
+    ($($c:ident)*) => {
+        impl_label_char!($($c => stringify!($c).as_bytes()[0]),*);
+    };
+}
+
+impl_label_chars_as_is!(
+    a b c d e f g h i j k l m n o p q r s t u v w x y z
+    A B C D E F G H I J K L M N O P Q R S T U V W X Y Z
+);
+
+impl_label_char!(
+    _0 => b'0', _1 => b'1', _2 => b'2', _3 => b'3', _4 => b'4',
+    _5 => b'5', _6 => b'6', _7 => b'7', _8 => b'8', _9 => b'9',
+    __ => b'_', _uc => UNICODE_START, uc_ => UNICODE_END,
+);
+
+/// A type-level label whose characters can be read back at compile time. Implemented for labels of up to 64
+/// characters, which covers any field name a struct is likely to have.
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` is not a field label of at most 64 characters",
+    note = "field names are only recovered from labels of up to 64 characters; consider shortening the field name"
+)]
+pub trait Label {
+    const ENCODED: &'static [u8];
+    const NAME: &'static str = Decoded::<Self>::NAME;
+}
+
+// frunk represents a one-character label as the bare character rather than a 1-tuple.
+impl<C: LabelChar> Label for C {
+    const ENCODED: &'static [u8] = &[C::BYTE];
+}
+
+macro_rules! impl_label_tuples {
+    ($c:ident) => {};
+    ($c:ident $($cs:ident)+) => {
+        impl<$c: LabelChar, $($cs: LabelChar),+> Label for ($c, $($cs),+) {
+            const ENCODED: &'static [u8] = &[$c::BYTE, $($cs::BYTE),+];
+        }
+
+        impl_label_tuples!($($cs)+);
+    };
+}
+
+impl_label_tuples!(
+    C00 C01 C02 C03 C04 C05 C06 C07 C08 C09 C10 C11 C12 C13 C14 C15
+    C16 C17 C18 C19 C20 C21 C22 C23 C24 C25 C26 C27 C28 C29 C30 C31
+    C32 C33 C34 C35 C36 C37 C38 C39 C40 C41 C42 C43 C44 C45 C46 C47
+    C48 C49 C50 C51 C52 C53 C54 C55 C56 C57 C58 C59 C60 C61 C62 C63
+);
+
+struct Decoded<L: ?Sized>(PhantomData<L>);
+
+impl<L: Label + ?Sized> Decoded<L> {
+    const BUF: &'static ([u8; MAX_NAME_LEN], usize) = &decode(L::ENCODED);
+    const NAME: &'static str = match std::str::from_utf8(Self::BUF.0.split_at(Self::BUF.1).0) {
+        Ok(name) => name,
+        Err(_) => panic!("label is not valid UTF-8"),
+    };
+}
+
+const fn hex_digit(byte: u8) -> u32 {
+    match byte {
+        b'0'..=b'9' => (byte - b'0') as u32,
+        b'a'..=b'f' => (byte - b'a' + 10) as u32,
+        _ => panic!("invalid hex digit in unicode label escape"),
+    }
+}
+
+/// Decode a label, expanding the `_uc u<hex> ... uc_` escapes frunk uses for non-identifier characters into UTF-8
+const fn decode(encoded: &[u8]) -> ([u8; MAX_NAME_LEN], usize) {
+    let mut out = [0; MAX_NAME_LEN];
+    let mut len = 0;
+    let mut i = 0;
+    while i < encoded.len() {
+        if encoded[i] != UNICODE_START {
+            out[len] = encoded[i];
+            len += 1;
+            i += 1;
+            continue;
+        }
+        i += 1;
+        while encoded[i] != UNICODE_END {
+            assert!(encoded[i] == b'u', "malformed unicode label escape");
+            i += 1;
+            let mut code = 0;
+            while encoded[i] != b'u' && encoded[i] != UNICODE_END {
+                code = code * 16 + hex_digit(encoded[i]);
+                i += 1;
+            }
+            let c = match char::from_u32(code) {
+                Some(c) => c,
+                None => panic!("invalid code point in unicode label escape"),
+            };
+            let mut buf = [0; 4];
+            let utf8 = c.encode_utf8(&mut buf).as_bytes();
+            let mut j = 0;
+            while j < utf8.len() {
+                out[len] = utf8[j];
+                len += 1;
+                j += 1;
+            }
+        }
+        i += 1;
+    }
+    (out, len)
+}
+
+/// The names of the fields of an HList of [Field]s
+pub trait FieldNames: HList {
+    type Names: ConsListT<&'static str>;
+
+    const NAMES: Self::Names;
+}
+
+impl FieldNames for HNil {
+    type Names = Nil<&'static str>;
+
+    const NAMES: Self::Names = Nil([]);
+}
+
+impl<Name: Label, T, Tail: FieldNames> FieldNames for HCons<Field<Name, T>, Tail> {
+    type Names = Cons<&'static str, Tail::Names>;
+
+    const NAMES: Self::Names = Cons(Name::NAME, Tail::NAMES);
+}
+
+pub(crate) const fn field_names<Repr: FieldNames>() -> ConsList<&'static str, Repr::Names> {
+    ConsList {
+        list: Repr::NAMES,
+        marker: PhantomData,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use frunk::LabelledGeneric;
+
+    use crate::WithLabelledGeneric;
+
+    #[allow(non_snake_case)]
+    #[derive(LabelledGeneric)]
+    struct Row {
+        id: u32,
+        x: u32,
+        first_name_2: String,
+        HTTPStatus: u16,
+        café: bool,
+    }
+
+    #[test]
+    fn field_names() {
+        assert_eq!(
+            Row::field_names().into_array(),
+            ["id", "x", "first_name_2", "HTTPStatus", "café"]
+        );
+    }
+}
@@ -0,0 +1,2208 @@
+//! Traversals over structs whose fields are futures
+
+use std::{
+    future::Future,
+    time::{Duration, Instant},
+};
+
+#[cfg(feature = "tokio")]
+use frunk::hlist::HMappable;
+use frunk::{
+    labelled::{field_with_name, Field},
+    prelude::HList,
+    Generic, HCons, HNil,
+};
+use futures::{TryFuture, TryFutureExt};
+
+use crate::{
+    cons_list::{Cons, Nil},
+    labelled::LabelledFunc,
+    validate::{FieldErrors, Validate},
+    ConsList, ConsListT, Folder, Func, MapToList, UnwrapResult,
+};
+
+#[cfg(feature = "tokio")]
+use crate::Poly;
+
+/// Await every field of an HList of futures, producing the HList of their outputs
+pub trait JoinFields: HList {
+    type Output: HList;
+
+    /// Await the fields one after another in declaration order
+    fn join_sequential(self) -> impl Future<Output = Self::Output>;
+
+    /// Poll all the fields concurrently within the current task
+    fn join_concurrent(self) -> impl Future<Output = Self::Output>;
+}
+
+impl JoinFields for HNil {
+    type Output = HNil;
+
+    async fn join_sequential(self) -> Self::Output {
+        HNil
+    }
+
+    async fn join_concurrent(self) -> Self::Output {
+        HNil
+    }
+}
+
+impl<Head: Future, Tail: JoinFields> JoinFields for HCons<Head, Tail> {
+    type Output = HCons<Head::Output, Tail::Output>;
+
+    async fn join_sequential(self) -> Self::Output {
+        let HCons { head, tail } = self;
+        let head = head.await;
+        HCons {
+            head,
+            tail: tail.join_sequential().await,
+        }
+    }
+
+    async fn join_concurrent(self) -> Self::Output {
+        let HCons { head, tail } = self;
+        let (head, tail) = futures::future::join(head, tail.join_concurrent()).await;
+        HCons { head, tail }
+    }
+}
+
+/// Map every field of a labelled HList into a future with a [LabelledFunc], which also receives each field's name,
+/// and await them all
+pub trait HMapNamedAsync<F>: HList {
+    type Output: HList;
+
+    /// Poll all the fields' futures concurrently within the current task
+    fn hmap_named_concurrent(self, f: F) -> impl Future<Output = Self::Output>;
+
+    /// Await each field's future before calling `f` on the next field
+    fn hmap_named_sequential(self, f: F) -> impl Future<Output = Self::Output>;
+}
+
+impl<F> HMapNamedAsync<F> for HNil {
+    type Output = HNil;
+
+    async fn hmap_named_concurrent(self, _f: F) -> Self::Output {
+        HNil
+    }
+
+    async fn hmap_named_sequential(self, _f: F) -> Self::Output {
+        HNil
+    }
+}
+
+impl<F: LabelledFunc<Head>, Name, Head, Tail: HMapNamedAsync<F>> HMapNamedAsync<F>
+    for HCons<Field<Name, Head>, Tail>
+where
+    F::Output: Future,
+{
+    type Output = HCons<Field<Name, <F::Output as Future>::Output>, Tail::Output>;
+
+    async fn hmap_named_concurrent(self, mut f: F) -> Self::Output {
+        let HCons { head, tail } = self;
+        let value = f.call(head.name, head.value);
+        let (value, tail) = futures::future::join(value, tail.hmap_named_concurrent(f)).await;
+        HCons {
+            head: field_with_name(head.name, value),
+            tail,
+        }
+    }
+
+    async fn hmap_named_sequential(self, mut f: F) -> Self::Output {
+        let HCons { head, tail } = self;
+        let value = f.call(head.name, head.value).await;
+        HCons {
+            head: field_with_name(head.name, value),
+            tail: tail.hmap_named_sequential(f).await,
+        }
+    }
+}
+
+/// The async counterpart of [Validate]: map every field of a labelled HList into a future
+/// producing a `Result`, and await them all concurrently
+pub trait ValidateAsync<F, E>: HList {
+    type Output;
+    /// The fields of the HList holding the `Result` of each field's future
+    type Results: Validate<UnwrapResult, E, Output = Self::Output>;
+
+    /// Call `f` on every field value and poll the resulting futures concurrently within the current task, keeping
+    /// each field's `Result`
+    fn join_results(self, f: F) -> impl Future<Output = Self::Results>;
+
+    /// Call `f` on every field value and poll the resulting futures concurrently within the current task. Every
+    /// future runs to completion, and the failures are reported in field declaration order.
+    fn validate_async(self, f: F) -> impl Future<Output = Result<Self::Output, FieldErrors<E>>> {
+        async move {
+            let mut errors = Vec::new();
+            Validate::validate(self.join_results(f).await, UnwrapResult, &mut errors).ok_or(errors)
+        }
+    }
+}
+
+impl<F, E> ValidateAsync<F, E> for HNil {
+    type Output = HNil;
+    type Results = HNil;
+
+    async fn join_results(self, _f: F) -> Self::Results {
+        HNil
+    }
+}
+
+impl<F: Func<Head>, E, Name, Head, Tail: ValidateAsync<F, E>> ValidateAsync<F, E>
+    for HCons<Field<Name, Head>, Tail>
+where
+    F::Output: TryFuture<Error = E>,
+{
+    type Output = HCons<Field<Name, <F::Output as TryFuture>::Ok>, Tail::Output>;
+    type Results = HCons<Field<Name, Result<<F::Output as TryFuture>::Ok, E>>, Tail::Results>;
+
+    async fn join_results(self, mut f: F) -> Self::Results {
+        let HCons { head, tail } = self;
+        let value = f.call(head.value).into_future();
+        let (value, tail) = futures::future::join(value, tail.join_results(f)).await;
+        HCons {
+            head: field_with_name(head.name, value),
+            tail,
+        }
+    }
+}
+
+/// Await every field of an HList of futures concurrently, recording how long each took
+pub trait JoinTimed: HList {
+    type Output: HList;
+    type Durations: ConsListT<Duration>;
+
+    fn join_timed(
+        self,
+        start: Instant,
+    ) -> impl Future<Output = (Self::Output, ConsList<Duration, Self::Durations>)>;
+}
+
+impl JoinTimed for HNil {
+    type Output = HNil;
+    type Durations = Nil<Duration>;
+
+    async fn join_timed(
+        self,
+        _start: Instant,
+    ) -> (Self::Output, ConsList<Duration, Self::Durations>) {
+        (HNil, ConsList::nil())
+    }
+}
+
+impl<Head: Future, Tail: JoinTimed> JoinTimed for HCons<Head, Tail> {
+    type Output = HCons<Head::Output, Tail::Output>;
+    type Durations = Cons<Duration, Tail::Durations>;
+
+    async fn join_timed(
+        self,
+        start: Instant,
+    ) -> (Self::Output, ConsList<Duration, Self::Durations>) {
+        let HCons { head, tail } = self;
+        let head = async {
+            let output = head.await;
+            (output, start.elapsed())
+        };
+        let ((head, elapsed), (tail, durations)) =
+            futures::future::join(head, tail.join_timed(start)).await;
+        (HCons { head, tail }, ConsList::cons(elapsed, durations))
+    }
+}
+
+/// Map every element of an HList into a future that can fail, and await them all, stopping at the first error
+pub trait TryHMapAsync<F, E>: HList {
+    type Output: HList;
+
+    /// Poll all the futures concurrently within the current task. The first error is returned as soon as it occurs,
+    /// dropping the futures that are still running, as with [try_join](futures::future::try_join).
+    fn try_hmap_concurrent(self, f: F) -> impl Future<Output = Result<Self::Output, E>>;
+
+    /// Await the futures one after another in declaration order. `f` isn't called on the elements after the first
+    /// one that fails.
+    fn try_hmap_sequential(self, f: F) -> impl Future<Output = Result<Self::Output, E>>;
+}
+
+impl<F, E> TryHMapAsync<F, E> for HNil {
+    type Output = HNil;
+
+    async fn try_hmap_concurrent(self, _f: F) -> Result<Self::Output, E> {
+        Ok(HNil)
+    }
+
+    async fn try_hmap_sequential(self, _f: F) -> Result<Self::Output, E> {
+        Ok(HNil)
+    }
+}
+
+impl<F: Func<Head>, E, Head, Tail: TryHMapAsync<F, E>> TryHMapAsync<F, E> for HCons<Head, Tail>
+where
+    F::Output: TryFuture<Error = E>,
+{
+    type Output = HCons<<F::Output as TryFuture>::Ok, Tail::Output>;
+
+    async fn try_hmap_concurrent(self, mut f: F) -> Result<Self::Output, E> {
+        let HCons { head, tail } = self;
+        let head = f.call(head);
+        let (head, tail) = futures::future::try_join(head, tail.try_hmap_concurrent(f)).await?;
+        Ok(HCons { head, tail })
+    }
+
+    async fn try_hmap_sequential(self, mut f: F) -> Result<Self::Output, E> {
+        let HCons { head, tail } = self;
+        let head = f.call(head).into_future().await?;
+        Ok(HCons {
+            head,
+            tail: tail.try_hmap_sequential(f).await?,
+        })
+    }
+}
+
+/// Like [Folder], but each step produces the next accumulator asynchronously
+pub trait AsyncFolder<Acc, I> {
+    fn fold_async(&mut self, acc: Acc, i: I) -> impl Future<Output = Acc>;
+}
+
+impl<F: AsyncFolder<Acc, I>, Acc, I> AsyncFolder<Acc, I> for &mut F {
+    fn fold_async(&mut self, acc: Acc, i: I) -> impl Future<Output = Acc> {
+        (*self).fold_async(acc, i)
+    }
+}
+
+/// Adapts a [Folder] into an [AsyncFolder] whose steps complete immediately, so a folder written for
+/// [hfold](crate::WithGeneric::hfold) can be reused with [hfold_async](WithGenericFutures::hfold_async)
+pub struct SyncFolder<F>(pub F);
+
+impl<F: Folder<Acc, I>, Acc, I> AsyncFolder<Acc, I> for SyncFolder<F> {
+    fn fold_async(&mut self, acc: Acc, i: I) -> impl Future<Output = Acc> {
+        std::future::ready(self.0.fold(acc, i))
+    }
+}
+
+/// Fold over an HList with an [AsyncFolder]
+pub trait HFoldAsync<F, Acc>: HList {
+    /// Await each step before starting the next, from first to last
+    fn hfold_async(self, init: Acc, f: F) -> impl Future<Output = Acc>;
+}
+
+impl<F, Acc> HFoldAsync<F, Acc> for HNil {
+    async fn hfold_async(self, init: Acc, _f: F) -> Acc {
+        init
+    }
+}
+
+impl<F: AsyncFolder<Acc, Head>, Acc, Head, Tail: HFoldAsync<F, Acc>> HFoldAsync<F, Acc>
+    for HCons<Head, Tail>
+{
+    async fn hfold_async(self, init: Acc, mut f: F) -> Acc {
+        let HCons { head, tail } = self;
+        let acc = f.fold_async(init, head).await;
+        tail.hfold_async(acc, f).await
+    }
+}
+
+/// Visit an HList from last to first with a [Func] producing futures, awaiting each one before moving on
+pub trait ForEachRevAsync<F>: HList {
+    fn for_each_rev_async(self, f: &mut F) -> impl Future<Output = ()>;
+}
+
+impl<F> ForEachRevAsync<F> for HNil {
+    async fn for_each_rev_async(self, _f: &mut F) {}
+}
+
+impl<F: Func<Head>, Head, Tail: ForEachRevAsync<F>> ForEachRevAsync<F> for HCons<Head, Tail>
+where
+    F::Output: Future<Output = ()>,
+{
+    async fn for_each_rev_async(self, f: &mut F) {
+        let HCons { head, tail } = self;
+        tail.for_each_rev_async(f).await;
+        f.call(head).await
+    }
+}
+
+/// Convenience functions for structs implementing [Generic] whose fields are all futures
+pub trait WithGenericFutures: Generic {
+    /// Await every field concurrently within the current task and collect the outputs into `U`
+    fn join_fields<U: Generic>(self) -> impl Future<Output = U>
+    where
+        Self::Repr: JoinFields<Output = U::Repr>;
+
+    /// Like [join_fields](WithGenericFutures::join_fields) but awaits each field only once the previous one has
+    /// completed
+    fn join_fields_sequential<U: Generic>(self) -> impl Future<Output = U>
+    where
+        Self::Repr: JoinFields<Output = U::Repr>;
+
+    /// Like [join_fields](WithGenericFutures::join_fields) but also reports how long each field took to complete, in
+    /// declaration order. Each field's time is measured from when the join is first polled.
+    fn join_fields_timed<U: Generic>(
+        self,
+    ) -> impl Future<Output = (U, ConsList<Duration, <Self::Repr as JoinTimed>::Durations>)>
+    where
+        Self::Repr: JoinTimed<Output = U::Repr>;
+
+    /// Map every field through `f` into a future with a common type and yield their outputs in declaration order, with
+    /// up to `n` of the futures in flight at a time. The struct-field analogue of
+    /// [StreamExt::buffered](futures::StreamExt::buffered). Panics if `n` is zero, since no future could ever be
+    /// polled.
+    fn map_to_stream_ordered<F, Fut: Future>(
+        self,
+        n: usize,
+        f: F,
+    ) -> impl futures::Stream<Item = Fut::Output>
+    where
+        Self::Repr: MapToList<F, Fut>;
+
+    /// Like [WithGeneric::hfold](crate::WithGeneric::hfold) for an [AsyncFolder] `f`. A sync [Folder] can be passed
+    /// by wrapping it in [SyncFolder].
+    /// Unlike the other methods here, the fields of `self` needn't be futures themselves; `f` is called with the
+    /// accumulator and each field in turn, and the future it returns is awaited before moving on to the next field.
+    fn hfold_async<Acc, F>(self, init: Acc, f: F) -> impl Future<Output = Acc>
+    where
+        Self::Repr: HFoldAsync<F, Acc>;
+
+    /// Like [WithGeneric::for_each_rev](crate::WithGeneric::for_each_rev) for a `f` that maps each field into a
+    /// future. The fields are visited from last to first and each future is awaited before `f` is called on the next
+    /// field, e.g. to shut down resources in the reverse of the order they were started.
+    fn for_each_rev_async<F>(self, f: F) -> impl Future<Output = ()>
+    where
+        Self::Repr: ForEachRevAsync<F>;
+
+    /// Like [WithGeneric::try_hmap](crate::WithGeneric::try_hmap) for a `f` that maps each field into a future
+    /// producing a `Result`. The futures are polled concurrently within the current task, and the first error is
+    /// returned as soon as it occurs, dropping the futures that haven't finished.
+    fn try_hmap_async<U: Generic, F, E>(self, f: F) -> impl Future<Output = Result<U, E>>
+    where
+        Self::Repr: TryHMapAsync<F, E, Output = U::Repr>;
+
+    /// Like [try_hmap_async](WithGenericFutures::try_hmap_async) but awaits each field's future before calling `f` on
+    /// the next field, so nothing after the first failure is started
+    fn try_hmap_async_sequential<U: Generic, F, E>(
+        self,
+        f: F,
+    ) -> impl Future<Output = Result<U, E>>
+    where
+        Self::Repr: TryHMapAsync<F, E, Output = U::Repr>;
+
+    /// Like [WithGeneric::try_for_each](crate::WithGeneric::try_for_each) for a `f` whose futures are polled
+    /// concurrently, stopping at the first error as with [try_hmap_async](WithGenericFutures::try_hmap_async)
+    fn try_for_each_async<F, E>(self, f: F) -> impl Future<Output = Result<(), E>>
+    where
+        Self::Repr: TryHMapAsync<F, E>;
+
+    /// Like [join_fields](WithGenericFutures::join_fields) but spawns every field onto the tokio runtime so they can
+    /// make progress on separate worker threads. Fails if any of the spawned tasks panicked or was cancelled.
+    #[cfg(feature = "tokio")]
+    fn join_fields_spawned<U: Generic>(
+        self,
+    ) -> impl Future<Output = Result<U, tokio::task::JoinError>>
+    where
+        Self::Repr: SpawnFields,
+        <Self::Repr as SpawnFields>::Handles: JoinHandles<Output = U::Repr>;
+
+    /// Map each field through `f` into a future and spawn every one of those futures onto the tokio runtime, collecting
+    /// their outputs into `U`. Unlike the other methods here, the fields of `self` needn't be futures themselves. Fails
+    /// if any of the spawned tasks panicked or was cancelled.
+    #[cfg(feature = "tokio")]
+    fn hmap_spawned<U: Generic, F>(
+        self,
+        f: F,
+    ) -> impl Future<Output = Result<U, tokio::task::JoinError>>
+    where
+        Self::Repr: HMappable<Poly<F>>,
+        <Self::Repr as HMappable<Poly<F>>>::Output: SpawnFields,
+        <<Self::Repr as HMappable<Poly<F>>>::Output as SpawnFields>::Handles:
+            JoinHandles<Output = U::Repr>;
+
+    /// Like [join_fields_spawned](WithGenericFutures::join_fields_spawned) but keeps going when a task panics or is
+    /// cancelled, so every field of `U` is the `Result` of the corresponding task
+    #[cfg(feature = "tokio")]
+    fn join_fields_spawned_isolated<U: Generic>(self) -> impl Future<Output = U>
+    where
+        Self::Repr: SpawnFields,
+        <Self::Repr as SpawnFields>::Handles: JoinFields<Output = U::Repr>;
+
+    /// Like [hmap_spawned](WithGenericFutures::hmap_spawned) but keeps going when a task panics or is cancelled, so
+    /// every field of `U` is the `Result` of the corresponding task
+    #[cfg(feature = "tokio")]
+    fn hmap_spawned_isolated<U: Generic, F>(self, f: F) -> impl Future<Output = U>
+    where
+        Self::Repr: HMappable<Poly<F>>,
+        <Self::Repr as HMappable<Poly<F>>>::Output: SpawnFields,
+        <<Self::Repr as HMappable<Poly<F>>>::Output as SpawnFields>::Handles:
+            JoinFields<Output = U::Repr>;
+
+    /// Run `f` on every field on tokio's blocking thread pool via
+    /// [spawn_blocking](tokio::task::spawn_blocking), for CPU-bound work that would otherwise stall the async runtime.
+    /// Each field gets its own clone of `f`. Fails if any of the calls panicked.
+    #[cfg(feature = "tokio")]
+    fn hmap_blocking<U: Generic, F>(
+        self,
+        f: F,
+    ) -> impl Future<Output = Result<U, tokio::task::JoinError>>
+    where
+        Self::Repr: SpawnBlocking<F>,
+        <Self::Repr as SpawnBlocking<F>>::Handles: JoinHandles<Output = U::Repr>;
+
+    /// Like [hmap_blocking](WithGenericFutures::hmap_blocking) for a `f` which is only called for its side effects
+    #[cfg(feature = "tokio")]
+    fn for_each_blocking<F>(self, f: F) -> impl Future<Output = Result<(), tokio::task::JoinError>>
+    where
+        Self::Repr: SpawnBlocking<F>,
+        <Self::Repr as SpawnBlocking<F>>::Handles: JoinHandles;
+
+    /// Map every field through `f` into a future and run each of those on its own task in a tokio
+    /// [JoinSet](tokio::task::JoinSet). Dropping the returned future aborts every task that is still running, as does
+    /// the first task to fail, in which case its error is returned.
+    #[cfg(feature = "tokio")]
+    fn par_for_each<F>(self, f: F) -> impl Future<Output = Result<(), tokio::task::JoinError>>
+    where
+        Self::Repr: SpawnOnto<F>;
+
+    /// Like [par_for_each](WithGenericFutures::par_for_each) but calls `progress` with the number of fields that have
+    /// completed so far and the total number of fields each time one of them finishes
+    #[cfg(feature = "tokio")]
+    fn par_for_each_with_progress<F>(
+        self,
+        f: F,
+        progress: impl FnMut(usize, usize),
+    ) -> impl Future<Output = Result<(), tokio::task::JoinError>>
+    where
+        Self::Repr: SpawnOnto<F>;
+
+    /// Like [par_for_each](WithGenericFutures::par_for_each) but runs at most `limit` of the fields' futures at a time.
+    /// Panics if `limit` is zero.
+    #[cfg(feature = "tokio")]
+    fn par_for_each_limited<F>(
+        self,
+        limit: usize,
+        f: F,
+    ) -> impl Future<Output = Result<(), tokio::task::JoinError>>
+    where
+        Self::Repr: SpawnOnto<Limited<F>>;
+
+    /// Like [hmap_spawned](WithGenericFutures::hmap_spawned) but runs at most `limit` of the fields' futures at a
+    /// time. Panics if `limit` is zero.
+    #[cfg(feature = "tokio")]
+    fn hmap_spawned_limited<U: Generic, F>(
+        self,
+        limit: usize,
+        f: F,
+    ) -> impl Future<Output = Result<U, tokio::task::JoinError>>
+    where
+        Self::Repr: HMappable<Poly<Limited<F>>>,
+        <Self::Repr as HMappable<Poly<Limited<F>>>>::Output: SpawnFields,
+        <<Self::Repr as HMappable<Poly<Limited<F>>>>::Output as SpawnFields>::Handles:
+            JoinHandles<Output = U::Repr>;
+
+    /// Await a struct of tokio [JoinHandle](tokio::task::JoinHandle)s into a struct of the task outputs, failing as
+    /// soon as any task has panicked or been cancelled. Use [join_fields](WithGenericFutures::join_fields) instead to
+    /// get a `Result` per field.
+    #[cfg(feature = "tokio")]
+    fn join_handles<U: Generic>(self) -> impl Future<Output = Result<U, tokio::task::JoinError>>
+    where
+        Self::Repr: JoinHandles<Output = U::Repr>;
+
+    /// Like [join_handles](WithGenericFutures::join_handles) but aborts every task that is still running once one of
+    /// them has failed
+    #[cfg(feature = "tokio")]
+    fn join_handles_abort_on_error<U: Generic>(
+        self,
+    ) -> impl Future<Output = Result<U, tokio::task::JoinError>>
+    where
+        Self::Repr: JoinHandles<Output = U::Repr>;
+
+    /// Combine a struct of tokio [watch](tokio::sync::watch) receivers into a stream of `U` which yields the current
+    /// values right away and then a fresh snapshot every time any of the fields changes. The stream ends once any of
+    /// the senders has been dropped.
+    #[cfg(feature = "tokio")]
+    fn watch_fields<U: Generic>(self) -> impl futures::Stream<Item = U>
+    where
+        Self::Repr: WatchFields<Output = U::Repr>;
+}
+
+impl<T: Generic> WithGenericFutures for T {
+    async fn join_fields<U: Generic>(self) -> U
+    where
+        Self::Repr: JoinFields<Output = U::Repr>,
+    {
+        Generic::from(Generic::into(self).join_concurrent().await)
+    }
+
+    async fn join_fields_sequential<U: Generic>(self) -> U
+    where
+        Self::Repr: JoinFields<Output = U::Repr>,
+    {
+        Generic::from(Generic::into(self).join_sequential().await)
+    }
+
+    async fn join_fields_timed<U: Generic>(
+        self,
+    ) -> (U, ConsList<Duration, <Self::Repr as JoinTimed>::Durations>)
+    where
+        Self::Repr: JoinTimed<Output = U::Repr>,
+    {
+        let (output, durations) = Generic::into(self).join_timed(Instant::now()).await;
+        (Generic::from(output), durations)
+    }
+
+    fn map_to_stream_ordered<F, Fut: Future>(
+        self,
+        n: usize,
+        f: F,
+    ) -> impl futures::Stream<Item = Fut::Output>
+    where
+        Self::Repr: MapToList<F, Fut>,
+    {
+        use futures::StreamExt;
+
+        assert!(n > 0, "buffer size must be at least 1");
+        futures::stream::iter(Generic::into(self).map_to_list(f)).buffered(n)
+    }
+
+    async fn hfold_async<Acc, F>(self, init: Acc, f: F) -> Acc
+    where
+        Self::Repr: HFoldAsync<F, Acc>,
+    {
+        Generic::into(self).hfold_async(init, f).await
+    }
+
+    async fn for_each_rev_async<F>(self, mut f: F)
+    where
+        Self::Repr: ForEachRevAsync<F>,
+    {
+        Generic::into(self).for_each_rev_async(&mut f).await
+    }
+
+    async fn try_hmap_async<U: Generic, F, E>(self, f: F) -> Result<U, E>
+    where
+        Self::Repr: TryHMapAsync<F, E, Output = U::Repr>,
+    {
+        Generic::into(self)
+            .try_hmap_concurrent(f)
+            .await
+            .map(Generic::from)
+    }
+
+    async fn try_hmap_async_sequential<U: Generic, F, E>(self, f: F) -> Result<U, E>
+    where
+        Self::Repr: TryHMapAsync<F, E, Output = U::Repr>,
+    {
+        Generic::into(self)
+            .try_hmap_sequential(f)
+            .await
+            .map(Generic::from)
+    }
+
+    async fn try_for_each_async<F, E>(self, f: F) -> Result<(), E>
+    where
+        Self::Repr: TryHMapAsync<F, E>,
+    {
+        Generic::into(self).try_hmap_concurrent(f).await?;
+        Ok(())
+    }
+
+    #[cfg(feature = "tokio")]
+    async fn join_fields_spawned<U: Generic>(self) -> Result<U, tokio::task::JoinError>
+    where
+        Self::Repr: SpawnFields,
+        <Self::Repr as SpawnFields>::Handles: JoinHandles<Output = U::Repr>,
+    {
+        let handles = Generic::into(self).spawn_fields();
+        Ok(Generic::from(handles.join_handles().await?))
+    }
+
+    #[cfg(feature = "tokio")]
+    async fn hmap_spawned<U: Generic, F>(self, f: F) -> Result<U, tokio::task::JoinError>
+    where
+        Self::Repr: HMappable<Poly<F>>,
+        <Self::Repr as HMappable<Poly<F>>>::Output: SpawnFields,
+        <<Self::Repr as HMappable<Poly<F>>>::Output as SpawnFields>::Handles:
+            JoinHandles<Output = U::Repr>,
+    {
+        let handles = Generic::into(self).map(Poly(f)).spawn_fields();
+        Ok(Generic::from(handles.join_handles().await?))
+    }
+
+    #[cfg(feature = "tokio")]
+    async fn join_fields_spawned_isolated<U: Generic>(self) -> U
+    where
+        Self::Repr: SpawnFields,
+        <Self::Repr as SpawnFields>::Handles: JoinFields<Output = U::Repr>,
+    {
+        let handles = Generic::into(self).spawn_fields();
+        Generic::from(handles.join_concurrent().await)
+    }
+
+    #[cfg(feature = "tokio")]
+    async fn hmap_spawned_isolated<U: Generic, F>(self, f: F) -> U
+    where
+        Self::Repr: HMappable<Poly<F>>,
+        <Self::Repr as HMappable<Poly<F>>>::Output: SpawnFields,
+        <<Self::Repr as HMappable<Poly<F>>>::Output as SpawnFields>::Handles:
+            JoinFields<Output = U::Repr>,
+    {
+        let handles = Generic::into(self).map(Poly(f)).spawn_fields();
+        Generic::from(handles.join_concurrent().await)
+    }
+
+    #[cfg(feature = "tokio")]
+    async fn hmap_blocking<U: Generic, F>(self, f: F) -> Result<U, tokio::task::JoinError>
+    where
+        Self::Repr: SpawnBlocking<F>,
+        <Self::Repr as SpawnBlocking<F>>::Handles: JoinHandles<Output = U::Repr>,
+    {
+        let handles = Generic::into(self).spawn_blocking(f);
+        Ok(Generic::from(handles.join_handles().await?))
+    }
+
+    #[cfg(feature = "tokio")]
+    async fn for_each_blocking<F>(self, f: F) -> Result<(), tokio::task::JoinError>
+    where
+        Self::Repr: SpawnBlocking<F>,
+        <Self::Repr as SpawnBlocking<F>>::Handles: JoinHandles,
+    {
+        let handles = Generic::into(self).spawn_blocking(f);
+        handles.join_handles().await?;
+        Ok(())
+    }
+
+    #[cfg(feature = "tokio")]
+    async fn par_for_each<F>(self, f: F) -> Result<(), tokio::task::JoinError>
+    where
+        Self::Repr: SpawnOnto<F>,
+    {
+        self.par_for_each_with_progress(f, |_, _| ()).await
+    }
+
+    #[cfg(feature = "tokio")]
+    async fn par_for_each_with_progress<F>(
+        self,
+        f: F,
+        mut progress: impl FnMut(usize, usize),
+    ) -> Result<(), tokio::task::JoinError>
+    where
+        Self::Repr: SpawnOnto<F>,
+    {
+        let mut tasks = tokio::task::JoinSet::new();
+        Generic::into(self).spawn_onto(f, &mut tasks);
+        let total = tasks.len();
+        let mut completed = 0;
+        while let Some(result) = tasks.join_next().await {
+            result?;
+            completed += 1;
+            progress(completed, total);
+        }
+        Ok(())
+    }
+
+    #[cfg(feature = "tokio")]
+    async fn par_for_each_limited<F>(self, limit: usize, f: F) -> Result<(), tokio::task::JoinError>
+    where
+        Self::Repr: SpawnOnto<Limited<F>>,
+    {
+        self.par_for_each(Limited::new(limit, f)).await
+    }
+
+    #[cfg(feature = "tokio")]
+    async fn hmap_spawned_limited<U: Generic, F>(
+        self,
+        limit: usize,
+        f: F,
+    ) -> Result<U, tokio::task::JoinError>
+    where
+        Self::Repr: HMappable<Poly<Limited<F>>>,
+        <Self::Repr as HMappable<Poly<Limited<F>>>>::Output: SpawnFields,
+        <<Self::Repr as HMappable<Poly<Limited<F>>>>::Output as SpawnFields>::Handles:
+            JoinHandles<Output = U::Repr>,
+    {
+        self.hmap_spawned(Limited::new(limit, f)).await
+    }
+
+    #[cfg(feature = "tokio")]
+    async fn join_handles<U: Generic>(self) -> Result<U, tokio::task::JoinError>
+    where
+        Self::Repr: JoinHandles<Output = U::Repr>,
+    {
+        Generic::into(self).join_handles().await.map(Generic::from)
+    }
+
+    #[cfg(feature = "tokio")]
+    async fn join_handles_abort_on_error<U: Generic>(self) -> Result<U, tokio::task::JoinError>
+    where
+        Self::Repr: JoinHandles<Output = U::Repr>,
+    {
+        let handles = Generic::into(self);
+        let mut abort_handles = Vec::new();
+        handles.abort_handles(&mut abort_handles);
+        let result = handles.join_handles().await;
+        if result.is_err() {
+            abort_handles
+                .iter()
+                .for_each(tokio::task::AbortHandle::abort);
+        }
+        result.map(Generic::from)
+    }
+
+    #[cfg(feature = "tokio")]
+    fn watch_fields<U: Generic>(self) -> impl futures::Stream<Item = U>
+    where
+        Self::Repr: WatchFields<Output = U::Repr>,
+    {
+        futures::stream::unfold(
+            (Generic::into(self), true),
+            |(mut receivers, first)| async move {
+                if !first {
+                    receivers.changed().await.ok()?;
+                }
+                let snapshot = Generic::from(receivers.snapshot());
+                Some((snapshot, (receivers, false)))
+            },
+        )
+    }
+}
+
+/// Spawn every field of an HList of futures onto the tokio runtime
+#[cfg(feature = "tokio")]
+pub trait SpawnFields: HList {
+    type Handles: HList;
+
+    fn spawn_fields(self) -> Self::Handles;
+}
+
+#[cfg(feature = "tokio")]
+impl SpawnFields for HNil {
+    type Handles = HNil;
+
+    fn spawn_fields(self) -> Self::Handles {
+        HNil
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl<Head, Tail: SpawnFields> SpawnFields for HCons<Head, Tail>
+where
+    Head: Future + Send + 'static,
+    Head::Output: Send + 'static,
+{
+    type Handles = HCons<tokio::task::JoinHandle<Head::Output>, Tail::Handles>;
+
+    fn spawn_fields(self) -> Self::Handles {
+        let HCons { head, tail } = self;
+        HCons {
+            head: tokio::spawn(head),
+            tail: tail.spawn_fields(),
+        }
+    }
+}
+
+/// Call a [Func] on every field of an HList on tokio's blocking thread pool
+#[cfg(feature = "tokio")]
+pub trait SpawnBlocking<F>: HList {
+    type Handles: HList;
+
+    fn spawn_blocking(self, f: F) -> Self::Handles;
+}
+
+#[cfg(feature = "tokio")]
+impl<F> SpawnBlocking<F> for HNil {
+    type Handles = HNil;
+
+    fn spawn_blocking(self, _f: F) -> Self::Handles {
+        HNil
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl<F, Head, Tail: SpawnBlocking<F>> SpawnBlocking<F> for HCons<Head, Tail>
+where
+    F: Func<Head> + Clone + Send + 'static,
+    F::Output: Send + 'static,
+    Head: Send + 'static,
+{
+    type Handles = HCons<tokio::task::JoinHandle<F::Output>, Tail::Handles>;
+
+    fn spawn_blocking(self, f: F) -> Self::Handles {
+        let HCons { head, tail } = self;
+        let mut head_f = f.clone();
+        HCons {
+            head: tokio::task::spawn_blocking(move || head_f.call(head)),
+            tail: tail.spawn_blocking(f),
+        }
+    }
+}
+
+/// Map every field of an HList through a [Func] into a future and spawn it onto a [JoinSet](tokio::task::JoinSet)
+#[cfg(feature = "tokio")]
+pub trait SpawnOnto<F>: HList {
+    fn spawn_onto(self, f: F, tasks: &mut tokio::task::JoinSet<()>);
+}
+
+#[cfg(feature = "tokio")]
+impl<F> SpawnOnto<F> for HNil {
+    fn spawn_onto(self, _f: F, _tasks: &mut tokio::task::JoinSet<()>) {}
+}
+
+#[cfg(feature = "tokio")]
+impl<F: Func<Head>, Head, Tail: SpawnOnto<F>> SpawnOnto<F> for HCons<Head, Tail>
+where
+    F::Output: Future<Output = ()> + Send + 'static,
+{
+    fn spawn_onto(self, mut f: F, tasks: &mut tokio::task::JoinSet<()>) {
+        tasks.spawn(f.call(self.head));
+        self.tail.spawn_onto(f, tasks)
+    }
+}
+
+/// Wraps the futures produced by a [Func] so that each waits for a permit before starting. Used by
+/// [par_for_each_limited](WithGenericFutures::par_for_each_limited) and
+/// [hmap_spawned_limited](WithGenericFutures::hmap_spawned_limited).
+#[cfg(feature = "tokio")]
+pub struct Limited<F> {
+    f: F,
+    permits: std::sync::Arc<tokio::sync::Semaphore>,
+}
+
+#[cfg(feature = "tokio")]
+impl<F> Limited<F> {
+    /// Let at most `limit` of the futures produced by `f` run at a time. Panics if `limit` is zero, since none of the
+    /// futures could ever start.
+    pub fn new(limit: usize, f: F) -> Self {
+        assert!(limit > 0, "concurrency limit must be at least 1");
+        Limited {
+            f,
+            permits: std::sync::Arc::new(tokio::sync::Semaphore::new(limit)),
+        }
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl<F: Func<I>, I> Func<I> for Limited<F>
+where
+    F::Output: Future + Send + 'static,
+    <F::Output as Future>::Output: Send,
+{
+    type Output = futures::future::BoxFuture<'static, <F::Output as Future>::Output>;
+
+    fn call(&mut self, i: I) -> Self::Output {
+        let permits = std::sync::Arc::clone(&self.permits);
+        let fut = self.f.call(i);
+        Box::pin(async move {
+            let _permit = permits.acquire_owned().await;
+            fut.await
+        })
+    }
+}
+
+/// Wraps the futures produced by a [Func] in [tokio::time::timeout], so that each resolves to
+/// `Err(Elapsed)` if it takes longer than the given duration. Combine with
+/// [hmap_spawned](WithGenericFutures::hmap_spawned), or with [WithGeneric::hmap](crate::WithGeneric::hmap) followed by
+/// [join_fields](WithGenericFutures::join_fields), to get a per-field `Result`.
+#[cfg(feature = "tokio")]
+pub struct Timeout<F> {
+    f: F,
+    duration: std::time::Duration,
+}
+
+#[cfg(feature = "tokio")]
+impl<F> Timeout<F> {
+    pub fn new(duration: std::time::Duration, f: F) -> Self {
+        Timeout { f, duration }
+    }
+
+    /// Instead of resolving to `Err(Elapsed)`, resolve each field that times out to the output of `g` on a clone of
+    /// the field's input
+    pub fn with_fallback<G>(self, g: G) -> TimeoutOr<F, G> {
+        TimeoutOr { timeout: self, g }
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl<F: Func<I>, I> Func<I> for Timeout<F>
+where
+    F::Output: Future,
+{
+    type Output = tokio::time::Timeout<F::Output>;
+
+    fn call(&mut self, i: I) -> Self::Output {
+        tokio::time::timeout(self.duration, self.f.call(i))
+    }
+}
+
+/// A [Timeout] that falls back to calling `G` on any field that takes too long. Created with
+/// [Timeout::with_fallback].
+#[cfg(feature = "tokio")]
+pub struct TimeoutOr<F, G> {
+    timeout: Timeout<F>,
+    g: G,
+}
+
+#[cfg(feature = "tokio")]
+impl<F: Func<I>, G, I: Clone + Send + 'static> Func<I> for TimeoutOr<F, G>
+where
+    F::Output: Future + Send + 'static,
+    <F::Output as Future>::Output: Send,
+    G: Func<I, Output = <F::Output as Future>::Output> + Clone + Send + 'static,
+{
+    type Output = futures::future::BoxFuture<'static, <F::Output as Future>::Output>;
+
+    fn call(&mut self, i: I) -> Self::Output {
+        let mut g = self.g.clone();
+        let fallback = i.clone();
+        let fut = self.timeout.call(i);
+        Box::pin(async move {
+            match fut.await {
+                Ok(output) => output,
+                Err(_) => g.call(fallback),
+            }
+        })
+    }
+}
+
+/// Wraps the futures produced by a [Func] so that each stops early, resolving to `None`, once `token` has been
+/// cancelled. Fields that completed before the cancellation keep their `Some` output, so traversing with this gives a
+/// partial result. Once `token` is cancelled, `f` isn't called at all for the remaining fields, and a future that
+/// hasn't resolved yet gives way to the cancellation even if it would be ready on its next poll.
+#[cfg(feature = "tokio-util")]
+pub struct Cancellable<F> {
+    f: F,
+    token: tokio_util::sync::CancellationToken,
+}
+
+#[cfg(feature = "tokio-util")]
+impl<F> Cancellable<F> {
+    pub fn new(token: tokio_util::sync::CancellationToken, f: F) -> Self {
+        Cancellable { f, token }
+    }
+}
+
+#[cfg(feature = "tokio-util")]
+impl<F: Func<I>, I> Func<I> for Cancellable<F>
+where
+    F::Output: Future + Send + 'static,
+    <F::Output as Future>::Output: Send,
+{
+    type Output = futures::future::BoxFuture<'static, Option<<F::Output as Future>::Output>>;
+
+    fn call(&mut self, i: I) -> Self::Output {
+        if self.token.is_cancelled() {
+            return Box::pin(std::future::ready(None));
+        }
+        let cancelled = self.token.clone().cancelled_owned();
+        let fut = self.f.call(i);
+        Box::pin(async move {
+            // `select` polls its first argument first, so cancellation wins over a future that is also ready
+            match futures::future::select(std::pin::pin!(cancelled), std::pin::pin!(fut)).await {
+                futures::future::Either::Left(_) => None,
+                futures::future::Either::Right((output, _)) => Some(output),
+            }
+        })
+    }
+}
+
+/// Wraps the futures produced by a [Func] so that each gives up at a shared deadline, resolving to `None` if it hasn't
+/// completed by then. Traversing with this gives a best-effort partial result that is ready by `deadline`.
+#[cfg(feature = "tokio")]
+pub struct Deadline<F> {
+    f: F,
+    deadline: tokio::time::Instant,
+}
+
+#[cfg(feature = "tokio")]
+impl<F> Deadline<F> {
+    pub fn new(deadline: tokio::time::Instant, f: F) -> Self {
+        Deadline { f, deadline }
+    }
+}
+
+#[cfg(feature = "tokio")]
+type CompletedBy<Fut> = fn(
+    Result<<Fut as Future>::Output, tokio::time::error::Elapsed>,
+) -> Option<<Fut as Future>::Output>;
+
+#[cfg(feature = "tokio")]
+impl<F: Func<I>, I> Func<I> for Deadline<F>
+where
+    F::Output: Future,
+{
+    type Output = futures::future::Map<tokio::time::Timeout<F::Output>, CompletedBy<F::Output>>;
+
+    fn call(&mut self, i: I) -> Self::Output {
+        use futures::FutureExt;
+
+        tokio::time::timeout_at(self.deadline, self.f.call(i))
+            .map(Result::ok as CompletedBy<F::Output>)
+    }
+}
+
+/// Decides whether and when a failed call is retried. Each field starts from a fresh clone of the policy.
+#[cfg(feature = "tokio")]
+pub trait RetryPolicy {
+    /// How long to wait before retrying after the `failures`th failed attempt, or `None` to give up
+    fn retry_after(&mut self, failures: u32) -> Option<std::time::Duration>;
+}
+
+/// Retries up to `max_attempts` times in total, doubling the delay after each failure starting from `initial`. The
+/// delay is capped at `max_delay` if one is given, and saturates at [Duration::MAX] otherwise.
+#[cfg(feature = "tokio")]
+#[derive(Clone, Copy, Debug)]
+pub struct ExponentialBackoff {
+    pub initial: std::time::Duration,
+    pub max_attempts: u32,
+    pub max_delay: Option<std::time::Duration>,
+}
+
+#[cfg(feature = "tokio")]
+impl RetryPolicy for ExponentialBackoff {
+    fn retry_after(&mut self, failures: u32) -> Option<std::time::Duration> {
+        (failures < self.max_attempts).then(|| {
+            let delay = if self.initial.is_zero() {
+                Duration::ZERO
+            } else {
+                2u32.checked_pow(failures.saturating_sub(1))
+                    .and_then(|factor| self.initial.checked_mul(factor))
+                    .unwrap_or(Duration::MAX)
+            };
+            self.max_delay
+                .map_or(delay, |max_delay| delay.min(max_delay))
+        })
+    }
+}
+
+/// Wraps a [Func] returning fallible futures so that a field whose future fails is retried according to a
+/// [RetryPolicy]. Every attempt calls a clone of the `Func` on a clone of the field.
+#[cfg(feature = "tokio")]
+pub struct Retrying<F, P> {
+    f: F,
+    policy: P,
+}
+
+#[cfg(feature = "tokio")]
+impl<F, P> Retrying<F, P> {
+    pub fn new(f: F, policy: P) -> Self {
+        Retrying { f, policy }
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl<F, P, I> Func<I> for Retrying<F, P>
+where
+    F: Func<I> + Clone + Send + 'static,
+    F::Output: futures::TryFuture + Send,
+    <F::Output as futures::TryFuture>::Ok: Send,
+    <F::Output as futures::TryFuture>::Error: Send,
+    P: RetryPolicy + Clone + Send + 'static,
+    I: Clone + Send + 'static,
+{
+    type Output = futures::future::BoxFuture<
+        'static,
+        Result<<F::Output as futures::TryFuture>::Ok, <F::Output as futures::TryFuture>::Error>,
+    >;
+
+    fn call(&mut self, i: I) -> Self::Output {
+        use futures::TryFutureExt;
+
+        let mut f = self.f.clone();
+        let mut policy = self.policy.clone();
+        Box::pin(async move {
+            let mut failures = 0;
+            loop {
+                match f.call(i.clone()).into_future().await {
+                    Ok(output) => return Ok(output),
+                    Err(err) => {
+                        failures += 1;
+                        match policy.retry_after(failures) {
+                            Some(delay) => tokio::time::sleep(delay).await,
+                            None => return Err(err),
+                        }
+                    }
+                }
+            }
+        })
+    }
+}
+
+/// A [LabelledFunc] that instruments the future `f` produces for each field with a `tracing` span named `field` whose
+/// `field` attribute holds the field's name, so slow fields can be told apart in logs and flamegraphs. Used by
+/// [hmap_async_traced](crate::WithLabelledGeneric::hmap_async_traced) and
+/// [for_each_async_traced](crate::WithLabelledGeneric::for_each_async_traced).
+#[cfg(feature = "tracing")]
+pub struct Traced<F>(pub F);
+
+#[cfg(feature = "tracing")]
+impl<F: Func<I>, I> LabelledFunc<I> for Traced<F>
+where
+    F::Output: Future,
+{
+    type Output = tracing::instrument::Instrumented<F::Output>;
+
+    fn call(&mut self, name: &'static str, i: I) -> Self::Output {
+        use tracing::Instrument;
+
+        self.0
+            .call(i)
+            .instrument(tracing::info_span!("field", field = name))
+    }
+}
+
+/// Await every field of an HList of tokio [JoinHandle](tokio::task::JoinHandle)s
+#[cfg(feature = "tokio")]
+pub trait JoinHandles: HList {
+    type Output: HList;
+
+    /// Await all the handles concurrently, stopping at the first task that failed. The tasks that haven't finished
+    /// by then keep running in the background.
+    fn join_handles(self) -> impl Future<Output = Result<Self::Output, tokio::task::JoinError>>;
+
+    /// Push a handle that can abort each task onto `handles`
+    fn abort_handles(&self, handles: &mut Vec<tokio::task::AbortHandle>);
+}
+
+#[cfg(feature = "tokio")]
+impl JoinHandles for HNil {
+    type Output = HNil;
+
+    async fn join_handles(self) -> Result<Self::Output, tokio::task::JoinError> {
+        Ok(HNil)
+    }
+
+    fn abort_handles(&self, _handles: &mut Vec<tokio::task::AbortHandle>) {}
+}
+
+#[cfg(feature = "tokio")]
+impl<Head, Tail: JoinHandles> JoinHandles for HCons<tokio::task::JoinHandle<Head>, Tail> {
+    type Output = HCons<Head, Tail::Output>;
+
+    async fn join_handles(self) -> Result<Self::Output, tokio::task::JoinError> {
+        let HCons { head, tail } = self;
+        let (head, tail) = futures::future::try_join(head, tail.join_handles()).await?;
+        Ok(HCons { head, tail })
+    }
+
+    fn abort_handles(&self, handles: &mut Vec<tokio::task::AbortHandle>) {
+        handles.push(self.head.abort_handle());
+        self.tail.abort_handles(handles);
+    }
+}
+
+/// An HList of tokio [watch](tokio::sync::watch) receivers
+#[cfg(feature = "tokio")]
+pub trait WatchFields: HList {
+    type Output: HList;
+
+    /// Clone the latest value of every receiver, marking them all as seen
+    fn snapshot(&mut self) -> Self::Output;
+
+    /// Wait until any of the receivers has a value that hasn't been seen yet. Fails if any of the senders has been
+    /// dropped.
+    fn changed(&mut self)
+        -> impl Future<Output = Result<(), tokio::sync::watch::error::RecvError>>;
+}
+
+#[cfg(feature = "tokio")]
+impl WatchFields for HNil {
+    type Output = HNil;
+
+    fn snapshot(&mut self) -> Self::Output {
+        HNil
+    }
+
+    async fn changed(&mut self) -> Result<(), tokio::sync::watch::error::RecvError> {
+        std::future::pending().await
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl<Head: Clone, Tail: WatchFields> WatchFields
+    for HCons<tokio::sync::watch::Receiver<Head>, Tail>
+{
+    type Output = HCons<Head, Tail::Output>;
+
+    fn snapshot(&mut self) -> Self::Output {
+        HCons {
+            head: self.head.borrow_and_update().clone(),
+            tail: self.tail.snapshot(),
+        }
+    }
+
+    async fn changed(&mut self) -> Result<(), tokio::sync::watch::error::RecvError> {
+        let head = std::pin::pin!(self.head.changed());
+        let tail = std::pin::pin!(self.tail.changed());
+        futures::future::select(head, tail).await.factor_first().0
+    }
+}
+
+/// The error returned by [fan_out] when the receiving half of one of the channels has been dropped
+#[cfg(feature = "tokio")]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ChannelClosed {
+    /// The position of the field whose channel was closed
+    pub position: usize,
+}
+
+#[cfg(feature = "tokio")]
+impl std::fmt::Display for ChannelClosed {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "channel for field {} is closed", self.position)
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl std::error::Error for ChannelClosed {}
+
+/// Send each field of an HList into the matching tokio [Sender](tokio::sync::mpsc::Sender) of an HList of senders
+#[cfg(feature = "tokio")]
+pub trait SendFields<Item>: HList {
+    /// Send the fields in declaration order, numbering them from `position`
+    fn send_fields(
+        &self,
+        item: Item,
+        position: usize,
+    ) -> impl Future<Output = Result<(), ChannelClosed>>;
+}
+
+#[cfg(feature = "tokio")]
+impl SendFields<HNil> for HNil {
+    async fn send_fields(&self, _item: HNil, _position: usize) -> Result<(), ChannelClosed> {
+        Ok(())
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl<Head, ItemTail, Tail: SendFields<ItemTail>> SendFields<HCons<Head, ItemTail>>
+    for HCons<tokio::sync::mpsc::Sender<Head>, Tail>
+{
+    async fn send_fields(
+        &self,
+        item: HCons<Head, ItemTail>,
+        position: usize,
+    ) -> Result<(), ChannelClosed> {
+        let HCons { head, tail } = item;
+        self.head
+            .send(head)
+            .await
+            .map_err(|_| ChannelClosed { position })?;
+        self.tail.send_fields(tail, position + 1).await
+    }
+}
+
+/// Split a stream of structs into columns by forwarding each field of every item into the matching channel of
+/// `senders`, a struct with one [Sender](tokio::sync::mpsc::Sender) per field. Stops at the end of the stream or as
+/// soon as one of the channels is closed. The senders are dropped when this returns, closing the channels.
+#[cfg(feature = "tokio")]
+pub async fn fan_out<T: Generic, Senders: Generic>(
+    stream: impl futures::Stream<Item = T>,
+    senders: Senders,
+) -> Result<(), ChannelClosed>
+where
+    Senders::Repr: SendFields<T::Repr>,
+{
+    use futures::StreamExt;
+
+    let senders = Generic::into(senders);
+    let mut stream = std::pin::pin!(stream);
+    while let Some(item) = stream.next().await {
+        senders.send_fields(Generic::into(item), 0).await?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        cell::RefCell,
+        future::{ready, Ready},
+    };
+
+    use futures::executor::block_on;
+
+    use super::*;
+
+    #[derive(Generic)]
+    struct Pending<A, B> {
+        a: A,
+        b: B,
+    }
+
+    #[derive(Generic, Debug, PartialEq)]
+    struct Done {
+        a: u32,
+        b: &'static str,
+    }
+
+    #[test]
+    fn join_fields() {
+        let pending: Pending<Ready<u32>, Ready<&str>> = Pending {
+            a: ready(1),
+            b: ready("x"),
+        };
+        let done: Done = block_on(pending.join_fields());
+        assert_eq!(done, Done { a: 1, b: "x" });
+    }
+
+    #[test]
+    fn validate_async() {
+        use frunk::LabelledGeneric;
+
+        use crate::WithLabelledGeneric;
+
+        #[derive(LabelledGeneric)]
+        struct Raw {
+            port: &'static str,
+            host: &'static str,
+            retries: &'static str,
+        }
+
+        #[derive(LabelledGeneric, Debug, PartialEq)]
+        struct Config {
+            port: u16,
+            host: u16,
+            retries: u16,
+        }
+
+        struct Parse;
+
+        impl Func<&'static str> for Parse {
+            type Output = Ready<Result<u16, std::num::ParseIntError>>;
+
+            fn call(&mut self, i: &'static str) -> Self::Output {
+                ready(i.parse())
+            }
+        }
+
+        let raw = Raw {
+            port: "80",
+            host: "1",
+            retries: "3",
+        };
+        assert_eq!(
+            block_on(raw.validate_async(Parse)),
+            Ok(Config {
+                port: 80,
+                host: 1,
+                retries: 3
+            })
+        );
+        let raw = Raw {
+            port: "x",
+            host: "1",
+            retries: "-1",
+        };
+        let errors = block_on(raw.validate_async::<Config, _, _>(Parse)).unwrap_err();
+        let names = errors.iter().map(|(name, _)| *name).collect::<Vec<_>>();
+        assert_eq!(names, ["port", "retries"]);
+    }
+
+    #[test]
+    fn labelled_hmap_async() {
+        use frunk::LabelledGeneric;
+
+        use crate::{labelled::LabelledFunc, WithLabelledGeneric};
+
+        #[derive(LabelledGeneric)]
+        struct Counts {
+            hits: u32,
+            misses: u32,
+        }
+
+        #[derive(LabelledGeneric, Debug, PartialEq)]
+        struct Labels {
+            hits: String,
+            misses: String,
+        }
+
+        struct Double;
+
+        impl Func<u32> for Double {
+            type Output = Ready<u32>;
+
+            fn call(&mut self, i: u32) -> Self::Output {
+                ready(i * 2)
+            }
+        }
+
+        struct Show;
+
+        impl LabelledFunc<u32> for Show {
+            type Output = Ready<String>;
+
+            fn call(&mut self, name: &'static str, i: u32) -> Self::Output {
+                ready(format!("{name}={i}"))
+            }
+        }
+
+        struct Push<'a>(&'a RefCell<Vec<u32>>);
+
+        impl Func<u32> for Push<'_> {
+            type Output = Ready<()>;
+
+            fn call(&mut self, i: u32) -> Self::Output {
+                self.0.borrow_mut().push(i);
+                ready(())
+            }
+        }
+
+        let doubled: Counts = block_on(Counts { hits: 1, misses: 2 }.hmap_async(Double));
+        assert_eq!((doubled.hits, doubled.misses), (2, 4));
+        let doubled: Counts = block_on(Counts { hits: 3, misses: 4 }.hmap_async_sequential(Double));
+        assert_eq!((doubled.hits, doubled.misses), (6, 8));
+        assert_eq!(
+            block_on(Counts { hits: 1, misses: 2 }.hmap_named_async::<Labels, _>(Show)),
+            Labels {
+                hits: "hits=1".to_owned(),
+                misses: "misses=2".to_owned()
+            }
+        );
+        let list = block_on(Counts { hits: 5, misses: 6 }.map_to_list_async(Double));
+        assert_eq!(list.into_iter().collect::<Vec<_>>(), [10, 12]);
+        let seen = RefCell::new(Vec::new());
+        block_on(Counts { hits: 7, misses: 8 }.for_each_async(Push(&seen)));
+        block_on(
+            Counts {
+                hits: 9,
+                misses: 10,
+            }
+            .for_each_async_sequential(Push(&seen)),
+        );
+        assert_eq!(*seen.borrow(), [7, 8, 9, 10]);
+    }
+
+    #[test]
+    fn join_fields_timed() {
+        let pending = Pending {
+            a: ready(1),
+            b: async {
+                std::thread::sleep(Duration::from_millis(5));
+                "x"
+            },
+        };
+        let (done, durations): (Done, _) = block_on(pending.join_fields_timed());
+        assert_eq!(done, Done { a: 1, b: "x" });
+        let [a, b] = durations.into_array();
+        assert!(a < b);
+        assert!(b >= Duration::from_millis(5));
+    }
+
+    #[test]
+    fn map_to_stream_ordered() {
+        use futures::StreamExt;
+
+        let delayed = crate::FnPoly(|i: u32| async move {
+            // Make the first field finish last
+            for _ in 0..(3 - i) {
+                let mut yielded = false;
+                futures::future::poll_fn(|cx| {
+                    if std::mem::replace(&mut yielded, true) {
+                        std::task::Poll::Ready(())
+                    } else {
+                        cx.waker().wake_by_ref();
+                        std::task::Poll::Pending
+                    }
+                })
+                .await;
+            }
+            i
+        });
+        let stream = Pending { a: 1, b: 2 }.map_to_stream_ordered(2, delayed);
+        assert_eq!(block_on(stream.collect::<Vec<_>>()), [1, 2]);
+    }
+
+    #[test]
+    #[should_panic = "buffer size must be at least 1"]
+    fn map_to_stream_ordered_zero() {
+        let _ = Pending { a: 1, b: 2 }.map_to_stream_ordered(0, crate::FnPoly(ready::<u32>));
+    }
+
+    #[test]
+    fn hfold_async() {
+        struct Append;
+
+        impl<T: std::fmt::Display> AsyncFolder<String, T> for Append {
+            async fn fold_async(&mut self, acc: String, i: T) -> String {
+                format!("{acc}{i}")
+            }
+        }
+
+        let folded = block_on(Pending { a: 1, b: "x" }.hfold_async(String::new(), Append));
+        assert_eq!(folded, "1x");
+
+        struct Sum;
+
+        impl<T: Into<u64>> Folder<u64, T> for Sum {
+            fn fold(&mut self, acc: u64, i: T) -> u64 {
+                acc + i.into()
+            }
+        }
+
+        let pending = Pending { a: 2u8, b: 3u32 };
+        assert_eq!(block_on(pending.hfold_async(1, SyncFolder(Sum))), 6);
+    }
+
+    #[test]
+    fn try_hmap_async() {
+        /// Fails on zero straight away, never finishes on odd numbers and succeeds on the rest
+        struct Check<'a>(&'a RefCell<Vec<u32>>);
+
+        impl Func<u32> for Check<'_> {
+            type Output = futures::future::Either<
+                Ready<Result<u32, &'static str>>,
+                std::future::Pending<Result<u32, &'static str>>,
+            >;
+
+            fn call(&mut self, i: u32) -> Self::Output {
+                self.0.borrow_mut().push(i);
+                match i {
+                    0 => futures::future::Either::Left(ready(Err("zero"))),
+                    i if i % 2 == 1 => futures::future::Either::Right(std::future::pending()),
+                    i => futures::future::Either::Left(ready(Ok(i))),
+                }
+            }
+        }
+
+        let calls = RefCell::new(Vec::new());
+        let done: Result<Pending<u32, u32>, _> =
+            block_on(Pending { a: 2, b: 4 }.try_hmap_async(Check(&calls)));
+        assert_eq!(done.map(|p| (p.a, p.b)), Ok((2, 4)));
+        // The pending field is dropped once the other one fails
+        assert_eq!(
+            block_on(
+                Pending { a: 1, b: 0 }.try_hmap_async::<Pending<u32, u32>, _, _>(Check(&calls))
+            )
+            .err(),
+            Some("zero")
+        );
+        assert_eq!(
+            block_on(Pending { a: 1, b: 0 }.try_for_each_async(Check(&calls))),
+            Err("zero")
+        );
+
+        calls.borrow_mut().clear();
+        let failed = block_on(
+            Pending { a: 0, b: 1 }
+                .try_hmap_async_sequential::<Pending<u32, u32>, _, _>(Check(&calls)),
+        );
+        assert_eq!(failed.err(), Some("zero"));
+        assert_eq!(*calls.borrow(), [0]);
+    }
+
+    #[test]
+    fn for_each_rev_async() {
+        let seen = RefCell::new(Vec::new());
+        let push = crate::FnPoly(|i: u32| {
+            let seen = &seen;
+            async move { seen.borrow_mut().push(i) }
+        });
+        block_on(Pending { a: 1, b: 2 }.for_each_rev_async(push));
+        assert_eq!(seen.into_inner(), [2, 1]);
+    }
+
+    #[test]
+    fn join_fields_sequential_preserves_order() {
+        let log = RefCell::new(Vec::new());
+        let pending = Pending {
+            a: async {
+                log.borrow_mut().push("a");
+                1
+            },
+            b: async {
+                log.borrow_mut().push("b");
+                "x"
+            },
+        };
+        let done: Done = block_on(pending.join_fields_sequential());
+        assert_eq!(done, Done { a: 1, b: "x" });
+        assert_eq!(*log.borrow(), ["a", "b"]);
+    }
+
+    #[cfg(feature = "tokio")]
+    #[tokio::test]
+    async fn join_fields_spawned() {
+        let pending = Pending {
+            a: async { 1 },
+            b: async { "x" },
+        };
+        let done: Done = pending.join_fields_spawned().await.unwrap();
+        assert_eq!(done, Done { a: 1, b: "x" });
+    }
+
+    #[cfg(feature = "tokio")]
+    #[tokio::test]
+    async fn join_fields_spawned_isolated() {
+        let pending = Pending {
+            a: async { 1 },
+            b: async { panic!("failed") },
+        };
+        let results: Pending<Result<u32, tokio::task::JoinError>, Result<&str, _>> =
+            pending.join_fields_spawned_isolated().await;
+        assert_eq!(results.a.unwrap(), 1);
+        assert!(results.b.unwrap_err().is_panic());
+    }
+
+    #[cfg(feature = "tokio")]
+    struct Yielding;
+
+    #[cfg(feature = "tokio")]
+    impl<T: Send + 'static> Func<T> for Yielding {
+        type Output = futures::future::BoxFuture<'static, T>;
+
+        fn call(&mut self, i: T) -> Self::Output {
+            Box::pin(async move {
+                tokio::task::yield_now().await;
+                i
+            })
+        }
+    }
+
+    #[cfg(feature = "tokio")]
+    #[tokio::test]
+    async fn hmap_spawned() {
+        let done: Done = Pending { a: 1, b: "x" }
+            .hmap_spawned(Yielding)
+            .await
+            .unwrap();
+        assert_eq!(done, Done { a: 1, b: "x" });
+    }
+
+    #[cfg(feature = "tokio")]
+    struct Explode;
+
+    #[cfg(feature = "tokio")]
+    impl Func<u32> for Explode {
+        type Output = Ready<u32>;
+
+        fn call(&mut self, i: u32) -> Self::Output {
+            ready(i)
+        }
+    }
+
+    #[cfg(feature = "tokio")]
+    impl Func<&'static str> for Explode {
+        type Output = futures::future::BoxFuture<'static, &'static str>;
+
+        fn call(&mut self, i: &'static str) -> Self::Output {
+            Box::pin(async move { panic!("{i}") })
+        }
+    }
+
+    #[cfg(feature = "tokio")]
+    #[tokio::test]
+    async fn hmap_spawned_isolated() {
+        let results: Pending<Result<u32, tokio::task::JoinError>, Result<&str, _>> =
+            Pending { a: 1, b: "x" }
+                .hmap_spawned_isolated(Explode)
+                .await;
+        assert_eq!(results.a.unwrap(), 1);
+        assert!(results.b.unwrap_err().is_panic());
+    }
+
+    #[cfg(feature = "tokio")]
+    #[derive(Clone)]
+    struct Checksum;
+
+    #[cfg(feature = "tokio")]
+    impl Func<Vec<u8>> for Checksum {
+        type Output = u32;
+
+        fn call(&mut self, i: Vec<u8>) -> u32 {
+            i.into_iter().map(u32::from).sum()
+        }
+    }
+
+    #[cfg(feature = "tokio")]
+    impl Func<&'static str> for Checksum {
+        type Output = &'static str;
+
+        fn call(&mut self, i: &'static str) -> &'static str {
+            i.trim()
+        }
+    }
+
+    #[cfg(feature = "tokio")]
+    #[derive(Clone, Default)]
+    struct Record(std::sync::Arc<std::sync::Mutex<Vec<u32>>>);
+
+    #[cfg(feature = "tokio")]
+    impl Func<u32> for Record {
+        type Output = ();
+
+        fn call(&mut self, i: u32) {
+            self.0.lock().unwrap().push(i);
+        }
+    }
+
+    #[cfg(feature = "tokio")]
+    #[tokio::test]
+    async fn hmap_blocking() {
+        let pending = Pending {
+            a: vec![1, 2, 3],
+            b: " x ",
+        };
+        let done: Done = pending.hmap_blocking(Checksum).await.unwrap();
+        assert_eq!(done, Done { a: 6, b: "x" });
+
+        let record = Record::default();
+        Pending { a: 1, b: 2 }
+            .for_each_blocking(record.clone())
+            .await
+            .unwrap();
+        let mut seen = record.0.lock().unwrap().clone();
+        seen.sort();
+        assert_eq!(seen, [1, 2]);
+    }
+
+    #[cfg(feature = "tokio")]
+    struct Park(tokio::sync::mpsc::Sender<()>);
+
+    #[cfg(feature = "tokio")]
+    impl<T> Func<T> for Park {
+        type Output = futures::future::BoxFuture<'static, ()>;
+
+        fn call(&mut self, _i: T) -> Self::Output {
+            let tx = self.0.clone();
+            Box::pin(async move {
+                let _tx = tx;
+                std::future::pending().await
+            })
+        }
+    }
+
+    #[cfg(feature = "tokio")]
+    #[tokio::test]
+    async fn par_for_each() {
+        use futures::FutureExt;
+
+        let record = Record::default();
+        let log = record.clone();
+        let push = crate::FnPoly(move |i: u32| {
+            let mut log = log.clone();
+            async move { log.call(i) }
+        });
+        Pending { a: 1, b: 2 }.par_for_each(push).await.unwrap();
+        let mut seen = record.0.lock().unwrap().clone();
+        seen.sort();
+        assert_eq!(seen, [1, 2]);
+
+        let (tx, mut rx) = tokio::sync::mpsc::channel(1);
+        let running = Pending { a: 1, b: "x" }.par_for_each(Park(tx));
+        assert!(running.now_or_never().is_none());
+        // Dropping the future aborted both tasks, dropping their senders.
+        assert_eq!(rx.recv().await, None);
+    }
+
+    #[cfg(feature = "tokio")]
+    #[tokio::test]
+    async fn par_for_each_with_progress() {
+        let mut reports = Vec::new();
+        Pending { a: 1, b: 2 }
+            .par_for_each_with_progress(crate::FnPoly(|_: u32| async {}), |done, total| {
+                reports.push((done, total))
+            })
+            .await
+            .unwrap();
+        assert_eq!(reports, [(1, 2), (2, 2)]);
+    }
+
+    #[cfg(feature = "tokio")]
+    #[tokio::test]
+    async fn par_for_each_limited() {
+        use std::sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc,
+        };
+
+        let running = Arc::new(AtomicUsize::new(0));
+        let peak = Arc::new(AtomicUsize::new(0));
+        let probe = {
+            let (running, peak) = (Arc::clone(&running), Arc::clone(&peak));
+            crate::FnPoly(move |_: u32| {
+                let (running, peak) = (Arc::clone(&running), Arc::clone(&peak));
+                async move {
+                    peak.fetch_max(running.fetch_add(1, Ordering::SeqCst) + 1, Ordering::SeqCst);
+                    tokio::task::yield_now().await;
+                    running.fetch_sub(1, Ordering::SeqCst);
+                }
+            })
+        };
+        Pending { a: 1, b: 2 }
+            .par_for_each_limited(1, probe)
+            .await
+            .unwrap();
+        assert_eq!(peak.load(Ordering::SeqCst), 1);
+    }
+
+    #[cfg(feature = "tokio")]
+    #[tokio::test]
+    #[should_panic = "concurrency limit must be at least 1"]
+    async fn par_for_each_limited_zero() {
+        let _ = Pending { a: 1, b: 2 }
+            .par_for_each_limited(0, crate::FnPoly(|_: u32| async {}))
+            .await;
+    }
+
+    #[cfg(feature = "tokio")]
+    #[tokio::test]
+    async fn hmap_spawned_limited() {
+        let done: Done = Pending { a: 1, b: "x" }
+            .hmap_spawned_limited(1, Yielding)
+            .await
+            .unwrap();
+        assert_eq!(done, Done { a: 1, b: "x" });
+    }
+
+    #[cfg(feature = "tokio")]
+    struct Stall;
+
+    #[cfg(feature = "tokio")]
+    impl Func<u32> for Stall {
+        type Output = Ready<u32>;
+
+        fn call(&mut self, i: u32) -> Self::Output {
+            ready(i)
+        }
+    }
+
+    #[cfg(feature = "tokio")]
+    impl Func<&'static str> for Stall {
+        type Output = std::future::Pending<&'static str>;
+
+        fn call(&mut self, _i: &'static str) -> Self::Output {
+            std::future::pending()
+        }
+    }
+
+    #[cfg(feature = "tokio")]
+    #[tokio::test]
+    async fn timeout() {
+        use tokio::time::error::Elapsed;
+
+        let timeout = Timeout::new(std::time::Duration::from_millis(1), Stall);
+        let results: Pending<Result<u32, Elapsed>, Result<&str, Elapsed>> =
+            Pending { a: 1, b: "x" }
+                .hmap_spawned(timeout)
+                .await
+                .unwrap();
+        assert_eq!(results.a, Ok(1));
+        assert!(results.b.is_err());
+    }
+
+    #[cfg(feature = "tokio")]
+    #[derive(Clone)]
+    struct Placeholder;
+
+    #[cfg(feature = "tokio")]
+    impl Func<u32> for Placeholder {
+        type Output = u32;
+
+        fn call(&mut self, _i: u32) -> Self::Output {
+            0
+        }
+    }
+
+    #[cfg(feature = "tokio")]
+    impl Func<&'static str> for Placeholder {
+        type Output = &'static str;
+
+        fn call(&mut self, i: &'static str) -> Self::Output {
+            assert_eq!(i, "x");
+            "timed out"
+        }
+    }
+
+    #[cfg(feature = "tokio")]
+    #[tokio::test]
+    async fn timeout_with_fallback() {
+        let timeout =
+            Timeout::new(std::time::Duration::from_millis(1), Stall).with_fallback(Placeholder);
+        let done: Done = Pending { a: 1, b: "x" }
+            .hmap_spawned(timeout)
+            .await
+            .unwrap();
+        assert_eq!(
+            done,
+            Done {
+                a: 1,
+                b: "timed out"
+            }
+        );
+    }
+
+    #[cfg(feature = "tokio")]
+    #[tokio::test]
+    async fn deadline() {
+        let deadline = tokio::time::Instant::now() + std::time::Duration::from_millis(1);
+        let partial: Pending<Option<u32>, Option<&str>> = Pending { a: 1, b: "x" }
+            .hmap_spawned(Deadline::new(deadline, Stall))
+            .await
+            .unwrap();
+        assert_eq!(partial.a, Some(1));
+        assert_eq!(partial.b, None);
+    }
+
+    #[cfg(feature = "tokio-util")]
+    #[tokio::test]
+    async fn cancellable() {
+        use crate::WithGeneric;
+
+        let token = tokio_util::sync::CancellationToken::new();
+        let pending: Pending<_, _> =
+            Pending { a: 1, b: "x" }.hmap(Cancellable::new(token.clone(), Stall));
+        let (results, ()) = futures::join!(
+            pending.join_fields::<Pending<Option<u32>, Option<&str>>>(),
+            async {
+                tokio::task::yield_now().await;
+                token.cancel()
+            }
+        );
+        assert_eq!(results.a, Some(1));
+        assert_eq!(results.b, None);
+    }
+
+    #[cfg(feature = "tokio-util")]
+    #[tokio::test]
+    async fn cancellable_after_cancel() {
+        let token = tokio_util::sync::CancellationToken::new();
+        token.cancel();
+        let calls = std::sync::atomic::AtomicU32::new(0);
+        let counted = crate::FnPoly(|i: u32| {
+            calls.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            ready(i)
+        });
+        let results: Pending<Option<u32>, Option<u32>> = Pending { a: 1, b: 2 }
+            .hmap_spawned(Cancellable::new(token, counted))
+            .await
+            .unwrap();
+        assert_eq!((results.a, results.b), (None, None));
+        assert_eq!(calls.into_inner(), 0);
+
+        // A future that was created before the cancellation loses to it even if it's already ready
+        let token = tokio_util::sync::CancellationToken::new();
+        let ready_first = Cancellable::new(token.clone(), crate::FnPoly(ready::<u32>)).call(1);
+        token.cancel();
+        assert_eq!(ready_first.await, None);
+    }
+
+    #[cfg(feature = "tokio")]
+    #[derive(Clone, Default)]
+    struct Flaky(std::sync::Arc<std::sync::atomic::AtomicU32>);
+
+    #[cfg(feature = "tokio")]
+    impl Func<u32> for Flaky {
+        type Output = Ready<Result<u32, &'static str>>;
+
+        fn call(&mut self, i: u32) -> Self::Output {
+            let calls = self.0.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            ready(if calls < 2 { Err("flaky") } else { Ok(i) })
+        }
+    }
+
+    #[cfg(feature = "tokio")]
+    #[tokio::test]
+    async fn retrying() {
+        let backoff = |max_attempts| ExponentialBackoff {
+            initial: std::time::Duration::ZERO,
+            max_attempts,
+            max_delay: None,
+        };
+        let results: Pending<Result<u32, &str>, Result<u32, &str>> = Pending { a: 1, b: 2 }
+            .hmap_spawned(Retrying::new(Flaky::default(), backoff(3)))
+            .await
+            .unwrap();
+        // The fields share a call counter, so the first two calls fail between them.
+        assert_eq!((results.a, results.b), (Ok(1), Ok(2)));
+
+        let results: Pending<Result<u32, &str>, Result<u32, &str>> = Pending { a: 1, b: 2 }
+            .hmap_spawned(Retrying::new(Flaky::default(), backoff(1)))
+            .await
+            .unwrap();
+        assert_eq!((results.a, results.b), (Err("flaky"), Err("flaky")));
+    }
+
+    #[cfg(feature = "tokio")]
+    #[test]
+    fn exponential_backoff() {
+        use std::time::Duration;
+
+        let mut backoff = ExponentialBackoff {
+            initial: Duration::from_millis(10),
+            max_attempts: 3,
+            max_delay: None,
+        };
+        assert_eq!(backoff.retry_after(1), Some(Duration::from_millis(10)));
+        assert_eq!(backoff.retry_after(2), Some(Duration::from_millis(20)));
+        assert_eq!(backoff.retry_after(3), None);
+
+        let mut unbounded = ExponentialBackoff {
+            initial: Duration::from_secs(1),
+            max_attempts: u32::MAX,
+            max_delay: None,
+        };
+        assert_eq!(unbounded.retry_after(0), Some(Duration::from_secs(1)));
+        assert_eq!(unbounded.retry_after(1_000), Some(Duration::MAX));
+        assert_eq!(unbounded.retry_after(u32::MAX - 1), Some(Duration::MAX));
+        let mut instant = ExponentialBackoff {
+            initial: Duration::ZERO,
+            ..unbounded
+        };
+        assert_eq!(instant.retry_after(1_000), Some(Duration::ZERO));
+
+        let mut capped = ExponentialBackoff {
+            max_delay: Some(Duration::from_secs(60)),
+            ..unbounded
+        };
+        assert_eq!(capped.retry_after(3), Some(Duration::from_secs(4)));
+        assert_eq!(capped.retry_after(7), Some(Duration::from_secs(60)));
+        assert_eq!(capped.retry_after(1_000), Some(Duration::from_secs(60)));
+    }
+
+    /// A `tracing` subscriber that records the `field` attribute of every span created while it's installed
+    #[cfg(feature = "tracing")]
+    #[derive(Clone, Default)]
+    struct SpanFields(std::sync::Arc<std::sync::Mutex<Vec<String>>>);
+
+    #[cfg(feature = "tracing")]
+    impl tracing::Subscriber for SpanFields {
+        fn enabled(&self, _metadata: &tracing::Metadata<'_>) -> bool {
+            true
+        }
+
+        fn new_span(&self, span: &tracing::span::Attributes<'_>) -> tracing::span::Id {
+            struct Visitor<'a>(&'a mut Vec<String>);
+
+            impl tracing::field::Visit for Visitor<'_> {
+                fn record_str(&mut self, field: &tracing::field::Field, value: &str) {
+                    if field.name() == "field" {
+                        self.0.push(value.to_string());
+                    }
+                }
+
+                fn record_debug(
+                    &mut self,
+                    _field: &tracing::field::Field,
+                    _value: &dyn std::fmt::Debug,
+                ) {
+                }
+            }
+
+            let mut fields = self.0.lock().unwrap();
+            span.record(&mut Visitor(&mut fields));
+            tracing::span::Id::from_u64(fields.len() as u64 + 1)
+        }
+
+        fn record(&self, _span: &tracing::span::Id, _values: &tracing::span::Record<'_>) {}
+
+        fn record_follows_from(&self, _span: &tracing::span::Id, _follows: &tracing::span::Id) {}
+
+        fn event(&self, _event: &tracing::Event<'_>) {}
+
+        fn enter(&self, _span: &tracing::span::Id) {}
+
+        fn exit(&self, _span: &tracing::span::Id) {}
+    }
+
+    #[cfg(feature = "tracing")]
+    #[test]
+    fn traced() {
+        use crate::WithLabelledGeneric;
+
+        #[derive(frunk::LabelledGeneric)]
+        struct Probes<A, B> {
+            db: A,
+            cache: B,
+        }
+
+        let spans = SpanFields::default();
+        let done: Probes<u32, u32> = tracing::subscriber::with_default(spans.clone(), || {
+            block_on(Probes { db: 1, cache: 2 }.hmap_async_traced(crate::FnPoly(ready::<u32>)))
+        });
+        assert_eq!((done.db, done.cache), (1, 2));
+        assert_eq!(*spans.0.lock().unwrap(), ["db", "cache"]);
+
+        let spans = SpanFields::default();
+        let seen = RefCell::new(Vec::new());
+        let push = crate::FnPoly(|i: u32| {
+            seen.borrow_mut().push(i);
+            ready(())
+        });
+        tracing::subscriber::with_default(spans.clone(), || {
+            block_on(Probes { db: 1, cache: 2 }.for_each_async_traced(push))
+        });
+        assert_eq!(seen.into_inner(), [1, 2]);
+        assert_eq!(*spans.0.lock().unwrap(), ["db", "cache"]);
+    }
+
+    #[cfg(feature = "tokio")]
+    #[tokio::test]
+    async fn join_handles() {
+        let handles = Pending {
+            a: tokio::spawn(async { 1 }),
+            b: tokio::spawn(async { "x" }),
+        };
+        let done: Done = handles.join_handles().await.unwrap();
+        assert_eq!(done, Done { a: 1, b: "x" });
+
+        let (tx, rx) = tokio::sync::oneshot::channel::<()>();
+        let handles = Pending {
+            a: tokio::spawn(async {
+                let _tx = tx;
+                std::future::pending::<u32>().await
+            }),
+            b: tokio::spawn(async { panic!("failed") }),
+        };
+        let err = handles
+            .join_handles_abort_on_error::<Done>()
+            .await
+            .unwrap_err();
+        assert!(err.is_panic());
+        // The pending task was aborted, dropping its sender.
+        assert!(rx.await.is_err());
+    }
+
+    #[cfg(feature = "tokio")]
+    #[tokio::test]
+    async fn watch_fields() {
+        use futures::StreamExt;
+        use tokio::sync::watch::channel;
+
+        let (a_tx, a_rx) = channel(1);
+        let (b_tx, b_rx) = channel("x");
+        let receivers = Pending { a: a_rx, b: b_rx };
+        let mut snapshots = std::pin::pin!(receivers.watch_fields::<Done>());
+        assert_eq!(snapshots.next().await, Some(Done { a: 1, b: "x" }));
+        b_tx.send("y").unwrap();
+        assert_eq!(snapshots.next().await, Some(Done { a: 1, b: "y" }));
+        a_tx.send(2).unwrap();
+        assert_eq!(snapshots.next().await, Some(Done { a: 2, b: "y" }));
+        drop(a_tx);
+        assert_eq!(snapshots.next().await, None);
+    }
+
+    #[cfg(feature = "tokio")]
+    #[tokio::test]
+    async fn fan_out() {
+        use tokio::sync::mpsc::{channel, Sender};
+
+        let (a_tx, mut a_rx) = channel(4);
+        let (b_tx, mut b_rx) = channel(4);
+        let senders: Pending<Sender<u32>, Sender<&str>> = Pending { a: a_tx, b: b_tx };
+        let items = futures::stream::iter([Done { a: 1, b: "x" }, Done { a: 2, b: "y" }]);
+        super::fan_out(items, senders).await.unwrap();
+        assert_eq!(
+            (a_rx.recv().await, a_rx.recv().await, a_rx.recv().await),
+            (Some(1), Some(2), None)
+        );
+        assert_eq!(
+            (b_rx.recv().await, b_rx.recv().await, b_rx.recv().await),
+            (Some("x"), Some("y"), None)
+        );
+
+        let (a_tx, a_rx) = channel(4);
+        let (b_tx, b_rx) = channel(4);
+        drop(b_rx);
+        let senders: Pending<Sender<u32>, Sender<&str>> = Pending { a: a_tx, b: b_tx };
+        let items = futures::stream::iter([Done { a: 1, b: "x" }]);
+        assert_eq!(
+            super::fan_out(items, senders).await,
+            Err(ChannelClosed { position: 1 })
+        );
+        drop(a_rx);
+    }
+}
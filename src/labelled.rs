@@ -0,0 +1,477 @@
+//! HList machinery specific to the [LabelledGeneric](frunk::LabelledGeneric) representation, where every element is a
+//! [Field] carrying the field's name.
+
+use std::{error::Error, fmt, marker::PhantomData};
+
+use frunk::{
+    labelled::{field_with_name, ByNameFieldPlucker, Field},
+    prelude::HList,
+    HCons, HNil,
+};
+
+use crate::{field_names::Label, Func, TransposeOptions, TryFunc};
+
+/// Like [Func] but also receives the name of the field the value came from
+pub trait LabelledFunc<I> {
+    type Output;
+
+    fn call(&mut self, name: &'static str, i: I) -> Self::Output;
+}
+
+impl<F: LabelledFunc<I>, I> LabelledFunc<I> for &mut F {
+    type Output = F::Output;
+
+    fn call(&mut self, name: &'static str, i: I) -> Self::Output {
+        (*self).call(name, i)
+    }
+}
+
+/// Strips the label from a [Field], leaving only its value
+pub struct FieldValue;
+
+impl<Name, T> Func<Field<Name, T>> for FieldValue {
+    type Output = T;
+
+    fn call(&mut self, i: Field<Name, T>) -> Self::Output {
+        i.value
+    }
+}
+
+/// Applies `F` to the value of a [Field] and pairs the result with the field's name
+pub struct Named<F>(pub F);
+
+impl<F: Func<T>, Name, T> Func<Field<Name, T>> for Named<F> {
+    type Output = (&'static str, F::Output);
+
+    fn call(&mut self, i: Field<Name, T>) -> Self::Output {
+        (i.name, self.0.call(i.value))
+    }
+}
+
+/// Adapts a [Func] into a [LabelledFunc] that ignores the name of each field
+pub struct IgnoreName<F>(pub F);
+
+impl<F: Func<I>, I> LabelledFunc<I> for IgnoreName<F> {
+    type Output = F::Output;
+
+    fn call(&mut self, _name: &'static str, i: I) -> Self::Output {
+        self.0.call(i)
+    }
+}
+
+/// A [TryFunc] over fields holding a `Result` that unwraps `Ok` values and tags errors with the field name
+pub struct UnwrapNamedResult;
+
+impl<Name, T, E> TryFunc<Field<Name, Result<T, E>>> for UnwrapNamedResult {
+    type Output = Field<Name, T>;
+    type Error = (&'static str, E);
+
+    fn try_call(&mut self, i: Field<Name, Result<T, E>>) -> Result<Self::Output, Self::Error> {
+        match i.value {
+            Ok(value) => Ok(field_with_name(i.name, value)),
+            Err(err) => Err((i.name, err)),
+        }
+    }
+}
+
+pub trait HMapNamed<F>: HList {
+    type Output: HList;
+
+    /// Map `f` over the field values, passing each field's name along with its value and keeping the labels
+    fn hmap_named(self, f: F) -> Self::Output;
+}
+
+impl<F> HMapNamed<F> for HNil {
+    type Output = HNil;
+
+    fn hmap_named(self, _f: F) -> Self::Output {
+        HNil
+    }
+}
+
+impl<F: LabelledFunc<Head>, Name, Head, Tail: HMapNamed<F>> HMapNamed<F>
+    for HCons<Field<Name, Head>, Tail>
+{
+    type Output = HCons<Field<Name, F::Output>, Tail::Output>;
+
+    fn hmap_named(self, mut f: F) -> Self::Output {
+        let HCons { head, tail } = self;
+        HCons {
+            head: field_with_name(head.name, f.call(head.name, head.value)),
+            tail: tail.hmap_named(f),
+        }
+    }
+}
+
+pub trait ForEachNamed<F>: HList {
+    fn for_each_named(self, f: F);
+}
+
+impl<F> ForEachNamed<F> for HNil {
+    fn for_each_named(self, _f: F) {}
+}
+
+impl<F: LabelledFunc<Head, Output = ()>, Name, Head, Tail: ForEachNamed<F>> ForEachNamed<F>
+    for HCons<Field<Name, Head>, Tail>
+{
+    fn for_each_named(self, mut f: F) {
+        let HCons { head, tail } = self;
+        f.call(head.name, head.value);
+        tail.for_each_named(f)
+    }
+}
+
+/// Returned when building a struct from a collection of named values that has no value for one of the fields
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MissingField(pub &'static str);
+
+impl fmt::Display for MissingField {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "missing field `{}`", self.0)
+    }
+}
+
+impl Error for MissingField {}
+
+pub trait TakeNamed<U>: HList {
+    /// Build the HList by calling `take` with the name of each field in declaration order, stopping at the first field
+    /// for which it returns `None`
+    fn take_named(take: &mut impl FnMut(&'static str) -> Option<U>) -> Result<Self, MissingField>;
+}
+
+impl<U> TakeNamed<U> for HNil {
+    fn take_named(_take: &mut impl FnMut(&'static str) -> Option<U>) -> Result<Self, MissingField> {
+        Ok(HNil)
+    }
+}
+
+impl<U, Name: Label, Tail: TakeNamed<U>> TakeNamed<U> for HCons<Field<Name, U>, Tail> {
+    fn take_named(take: &mut impl FnMut(&'static str) -> Option<U>) -> Result<Self, MissingField> {
+        let value = take(Name::NAME).ok_or(MissingField(Name::NAME))?;
+        Ok(HCons {
+            head: field_with_name(Name::NAME, value),
+            tail: Tail::take_named(take)?,
+        })
+    }
+}
+
+/// Build a labelled HList by calling `F` once per field with the field's name and a [PhantomData] token for its type
+pub trait BuildNamed<F>: HList {
+    fn build_named(f: F) -> Self;
+}
+
+impl<F> BuildNamed<F> for HNil {
+    fn build_named(_f: F) -> Self {
+        HNil
+    }
+}
+
+impl<F: LabelledFunc<PhantomData<T>, Output = T>, Name: Label, T, Tail: BuildNamed<F>> BuildNamed<F>
+    for HCons<Field<Name, T>, Tail>
+{
+    fn build_named(mut f: F) -> Self {
+        HCons {
+            head: field_with_name(Name::NAME, f.call(Name::NAME, PhantomData)),
+            tail: Tail::build_named(f),
+        }
+    }
+}
+
+pub trait SculptMap<Source, F, Indices>: HList {
+    /// Build this HList of fields by plucking each field by name out of `source` and mapping its value with `f`.
+    /// Fields of `source` that don't appear in this HList are dropped without being passed to `f`.
+    fn sculpt_map(source: Source, f: F) -> Self;
+}
+
+impl<Source, F> SculptMap<Source, F, HNil> for HNil {
+    fn sculpt_map(_source: Source, _f: F) -> Self {
+        HNil
+    }
+}
+
+impl<Source, F, Name, T, Tail, Index, TailIndices> SculptMap<Source, F, HCons<Index, TailIndices>>
+    for HCons<Field<Name, T>, Tail>
+where
+    Source: ByNameFieldPlucker<Name, Index>,
+    F: Func<Source::TargetValue, Output = T>,
+    Tail: SculptMap<Source::Remainder, F, TailIndices>,
+{
+    fn sculpt_map(source: Source, mut f: F) -> Self {
+        let (head, remainder) = source.pluck_by_name();
+        HCons {
+            head: field_with_name(head.name, f.call(head.value)),
+            tail: Tail::sculpt_map(remainder, f),
+        }
+    }
+}
+
+impl<Name, Head, Tail: TransposeOptions> TransposeOptions
+    for HCons<Field<Name, Option<Head>>, Tail>
+{
+    type Output = HCons<Field<Name, Head>, Tail::Output>;
+
+    fn transpose_options(self) -> Option<Self::Output> {
+        let HCons { head, tail } = self;
+        Some(HCons {
+            head: field_with_name(head.name, head.value?),
+            tail: tail.transpose_options()?,
+        })
+    }
+}
+
+pub trait LabelledHZip3<B, C>: HList {
+    type Zipped: HList;
+
+    /// Zip the values of three HLists of fields with matching names into an HList of fields holding triples
+    fn labelled_hzip3(self, b: B, c: C) -> Self::Zipped;
+}
+
+impl LabelledHZip3<HNil, HNil> for HNil {
+    type Zipped = HNil;
+
+    fn labelled_hzip3(self, _b: HNil, _c: HNil) -> Self::Zipped {
+        HNil
+    }
+}
+
+impl<Name, A, B, C, ATail: LabelledHZip3<BTail, CTail>, BTail, CTail>
+    LabelledHZip3<HCons<Field<Name, B>, BTail>, HCons<Field<Name, C>, CTail>>
+    for HCons<Field<Name, A>, ATail>
+{
+    type Zipped = HCons<Field<Name, (A, B, C)>, ATail::Zipped>;
+
+    fn labelled_hzip3(
+        self,
+        b: HCons<Field<Name, B>, BTail>,
+        c: HCons<Field<Name, C>, CTail>,
+    ) -> Self::Zipped {
+        HCons {
+            head: field_with_name(
+                self.head.name,
+                (self.head.value, b.head.value, c.head.value),
+            ),
+            tail: self.tail.labelled_hzip3(b.tail, c.tail),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use frunk::LabelledGeneric;
+
+    use super::*;
+    use crate::WithLabelledGeneric;
+
+    #[derive(LabelledGeneric, Clone, Copy)]
+    struct Stats {
+        hits: u32,
+        misses: u32,
+    }
+
+    #[derive(LabelledGeneric, Debug, PartialEq)]
+    struct Deltas {
+        hits: (u32, u32, u32),
+        misses: (u32, u32, u32),
+    }
+
+    #[test]
+    fn named_traversal() {
+        struct Log<'a>(&'a mut Vec<String>);
+
+        impl LabelledFunc<u32> for Log<'_> {
+            type Output = ();
+
+            fn call(&mut self, name: &'static str, i: u32) {
+                self.0.push(format!("{name}={i}"));
+            }
+        }
+
+        struct ScaleMisses;
+
+        impl LabelledFunc<u32> for ScaleMisses {
+            type Output = u32;
+
+            fn call(&mut self, name: &'static str, i: u32) -> u32 {
+                if name == "misses" {
+                    i * 10
+                } else {
+                    i
+                }
+            }
+        }
+
+        let stats: Stats = Stats { hits: 3, misses: 4 }.hmap_named(ScaleMisses);
+        let mut log = Vec::new();
+        stats.for_each_named(Log(&mut log));
+        assert_eq!(log, vec!["hits=3", "misses=40"]);
+    }
+
+    #[test]
+    fn build_with_named() {
+        struct NameLength;
+
+        impl LabelledFunc<PhantomData<u32>> for NameLength {
+            type Output = u32;
+
+            fn call(&mut self, name: &'static str, _i: PhantomData<u32>) -> u32 {
+                name.len() as u32
+            }
+        }
+
+        let stats = Stats::build_with_named(NameLength);
+        assert_eq!((stats.hits, stats.misses), (4, 6));
+    }
+
+    #[test]
+    fn map_to_named_list() {
+        let stats = Stats { hits: 3, misses: 4 };
+        let named = stats.map_to_named_list(crate::Identity);
+        assert_eq!(named.into_array(), [("hits", 3), ("misses", 4)]);
+    }
+
+    #[test]
+    fn hashmap_round_trip() {
+        use std::collections::HashMap;
+
+        let map = Stats { hits: 3, misses: 4 }.to_hashmap();
+        assert_eq!(map, HashMap::from([("hits", 3), ("misses", 4)]));
+        let stats = Stats::from_hashmap(map).unwrap();
+        assert_eq!((stats.hits, stats.misses), (3, 4));
+
+        let partial = HashMap::from([(String::from("hits"), 1)]);
+        assert_eq!(
+            Stats::from_hashmap(partial).err(),
+            Some(MissingField("misses"))
+        );
+    }
+
+    #[test]
+    fn ordered_named_access() {
+        let stats = Stats { misses: 4, hits: 3 };
+        assert_eq!(
+            stats.iter_named().collect::<Vec<_>>(),
+            vec![("hits", &3), ("misses", &4)]
+        );
+        assert_eq!(
+            stats.to_btreemap().into_iter().collect::<Vec<_>>(),
+            vec![("hits", 3), ("misses", 4)]
+        );
+    }
+
+    #[test]
+    fn hmap_sculpted() {
+        #[derive(LabelledGeneric)]
+        struct Source {
+            id: u32,
+            name: &'static str,
+            secret: &'static str,
+        }
+
+        #[derive(LabelledGeneric, Debug, PartialEq)]
+        struct Public {
+            name: String,
+            id: String,
+        }
+
+        struct Show<'a>(&'a mut Vec<String>);
+
+        impl<T: ToString> Func<T> for Show<'_> {
+            type Output = String;
+
+            fn call(&mut self, i: T) -> String {
+                let shown = i.to_string();
+                self.0.push(shown.clone());
+                shown
+            }
+        }
+
+        let source = Source {
+            id: 7,
+            name: "ada",
+            secret: "hunter2",
+        };
+        let mut shown = Vec::new();
+        let public: Public = source.hmap_sculpted(Show(&mut shown));
+        assert_eq!(
+            public,
+            Public {
+                name: "ada".to_string(),
+                id: "7".to_string()
+            }
+        );
+        assert_eq!(shown, vec!["ada", "7"]);
+    }
+
+    #[test]
+    fn fields_into_iter() {
+        let stats = Stats { hits: 3, misses: 4 };
+        assert_eq!(
+            stats.fields_into_iter::<u32>().collect::<Vec<_>>(),
+            vec![3, 4]
+        );
+    }
+
+    #[test]
+    fn hzip3() {
+        let current = Stats { hits: 3, misses: 4 };
+        let previous = Stats { hits: 1, misses: 2 };
+        let delta = Stats { hits: 2, misses: 2 };
+        let zipped: Deltas = current.hzip3(previous, delta);
+        assert_eq!(
+            zipped,
+            Deltas {
+                hits: (3, 1, 2),
+                misses: (4, 2, 2)
+            }
+        );
+    }
+
+    #[test]
+    fn transpose_options() {
+        #[derive(LabelledGeneric)]
+        struct PartialStats {
+            hits: Option<u32>,
+            misses: Option<u32>,
+        }
+
+        let stats: Option<Stats> = PartialStats {
+            hits: Some(3),
+            misses: Some(4),
+        }
+        .transpose_options();
+        assert_eq!(stats.map(|s| (s.hits, s.misses)), Some((3, 4)));
+        let stats: Option<Stats> = PartialStats {
+            hits: Some(3),
+            misses: None,
+        }
+        .transpose_options();
+        assert!(stats.is_none());
+    }
+
+    #[test]
+    fn transpose_results() {
+        #[derive(LabelledGeneric)]
+        struct ParsedStats {
+            hits: Result<u32, &'static str>,
+            misses: Result<u32, &'static str>,
+        }
+
+        let stats: Result<Stats, _> = ParsedStats {
+            hits: Ok(3),
+            misses: Ok(4),
+        }
+        .transpose_results();
+        assert_eq!(stats.map(|s| (s.hits, s.misses)), Ok((3, 4)));
+        let failed = || ParsedStats {
+            hits: Err("bad hits"),
+            misses: Err("bad misses"),
+        };
+        let stats: Result<Stats, _> = failed().transpose_results();
+        assert_eq!(stats.err(), Some(("hits", "bad hits")));
+        let stats: Result<Stats, _> = failed().transpose_results_all();
+        assert_eq!(
+            stats.err(),
+            Some(vec![("hits", "bad hits"), ("misses", "bad misses")])
+        );
+    }
+}
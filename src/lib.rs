@@ -1,14 +1,58 @@
 //! Utilities for working with frunk.
 
+// Lets the code generated by frunk_utils_derive refer to `::frunk_utils` from within this crate's own tests.
+#[cfg(all(test, feature = "derive"))]
+extern crate self as frunk_utils;
+
+use std::{
+    borrow::Borrow,
+    collections::{BTreeMap, HashMap},
+    error::Error,
+    fmt,
+    hash::{BuildHasher, Hash},
+    marker::PhantomData,
+};
+
 use frunk::{
     hlist::{HMappable, HZippable},
+    indices::{Here, There},
+    labelled::IntoLabelledGeneric,
     prelude::HList,
-    Generic, HCons, HNil, LabelledGeneric,
+    Generic, HCons, HNil, LabelledGeneric, ToMut, ToRef,
 };
 
-pub use self::cons_list::{ConsList, ConsListT};
+#[cfg(feature = "derive")]
+pub use frunk_utils_derive::Fieldwise;
+
+#[cfg(feature = "futures")]
+pub use self::future::WithGenericFutures;
 
+pub use self::{
+    combinators::FuncExt,
+    cons_list::{ConsList, ConsListT},
+    coproduct::WithCoproductGeneric,
+    labelled::LabelledFunc,
+};
+
+pub mod builder;
+pub mod combinators;
 pub mod cons_list;
+pub mod coproduct;
+#[cfg(feature = "csv")]
+pub mod csv;
+pub mod env;
+pub mod field_names;
+#[cfg(feature = "futures")]
+pub mod future;
+pub mod labelled;
+pub mod parallel;
+pub mod parse;
+pub mod partial;
+pub mod row;
+pub mod sync;
+pub mod transmogrify;
+pub mod uninit;
+pub mod validate;
 
 /// The Func trait from frunk doesn't take `self` as a parameter to `call` so there isn't an easy way to get context
 /// from the surrounding scope. Here we define our own `Poly` wrapper and `Func` trait that does take `self` as a
@@ -29,6 +73,166 @@ impl<F: Func<I>, I> Func<I> for &mut F {
     }
 }
 
+/// Adapts a closure into a [Func]. A closure can only accept a single input type, so this is useful when every field
+/// being visited has the same type.
+#[derive(Clone, Copy)]
+pub struct FnPoly<F>(pub F);
+
+impl<F: FnMut(I) -> O, I, O> Func<I> for FnPoly<F> {
+    type Output = O;
+
+    fn call(&mut self, i: I) -> Self::Output {
+        (self.0)(i)
+    }
+}
+
+/// Adapts a stateless [frunk::Func] implementor into a [Func] so it can be used with the traversals in this crate
+pub struct FrunkFuncAdapter<F>(PhantomData<fn() -> F>);
+
+impl<F> FrunkFuncAdapter<F> {
+    pub fn new() -> Self {
+        FrunkFuncAdapter(PhantomData)
+    }
+}
+
+impl<F> Default for FrunkFuncAdapter<F> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<F: frunk::Func<I>, I> Func<I> for FrunkFuncAdapter<F> {
+    type Output = F::Output;
+
+    fn call(&mut self, i: I) -> Self::Output {
+        F::call(i)
+    }
+}
+
+/// A fallible counterpart to [Func]. Traversals driven by a `TryFunc` stop at the first field that produces an error.
+pub trait TryFunc<I> {
+    type Output;
+    type Error;
+
+    fn try_call(&mut self, i: I) -> Result<Self::Output, Self::Error>;
+}
+
+impl<F: TryFunc<I>, I> TryFunc<I> for &mut F {
+    type Output = F::Output;
+    type Error = F::Error;
+
+    fn try_call(&mut self, i: I) -> Result<Self::Output, Self::Error> {
+        (*self).try_call(i)
+    }
+}
+
+/// A binary counterpart to [Func] used to combine the corresponding fields of two structs.
+pub trait Func2<A, B> {
+    type Output;
+
+    fn call(&mut self, a: A, b: B) -> Self::Output;
+}
+
+impl<F: Func2<A, B>, A, B> Func2<A, B> for &mut F {
+    type Output = F::Output;
+
+    fn call(&mut self, a: A, b: B) -> Self::Output {
+        (*self).call(a, b)
+    }
+}
+
+/// A ternary counterpart to [Func] used to combine the corresponding fields of three structs.
+pub trait Func3<A, B, C> {
+    type Output;
+
+    fn call(&mut self, a: A, b: B, c: C) -> Self::Output;
+}
+
+impl<F: Func3<A, B, C>, A, B, C> Func3<A, B, C> for &mut F {
+    type Output = F::Output;
+
+    fn call(&mut self, a: A, b: B, c: C) -> Self::Output {
+        (*self).call(a, b, c)
+    }
+}
+
+/// Like [Func], [Folder] takes `self` so that folding over the fields of a struct can make use of context from the
+/// surrounding scope.
+pub trait Folder<Acc, I> {
+    fn fold(&mut self, acc: Acc, i: I) -> Acc;
+}
+
+impl<F: Folder<Acc, I>, Acc, I> Folder<Acc, I> for &mut F {
+    fn fold(&mut self, acc: Acc, i: I) -> Acc {
+        (*self).fold(acc, i)
+    }
+}
+
+/// Combines [Func] and [Folder]: maps each field while threading an accumulator through the traversal
+pub trait Scanner<Acc, I> {
+    type Output;
+
+    fn scan(&mut self, acc: Acc, i: I) -> (Acc, Self::Output);
+}
+
+impl<F: Scanner<Acc, I>, Acc, I> Scanner<Acc, I> for &mut F {
+    type Output = F::Output;
+
+    fn scan(&mut self, acc: Acc, i: I) -> (Acc, Self::Output) {
+        (*self).scan(acc, i)
+    }
+}
+
+/// Define an ad-hoc [Func] for several input types at once, in the style of frunk's `poly_fn!`, but with context
+/// captured from the surrounding scope. Each captured field is declared with its type and initial value and is bound by
+/// mutable reference inside every case. Lifetimes used by the field types are declared up front.
+///
+/// ```
+/// # use frunk_utils::{ctx_poly, Func};
+/// let mut log = Vec::new();
+/// let mut f = ctx_poly! {
+///     <'a> { log: &'a mut Vec<String> = &mut log, offset: i64 = 10 }
+///     |x: i32| -> i64 { log.push(format!("int {x}")); i64::from(x) + *offset }
+///     |s: &str| -> usize { log.push(format!("str {s}")); s.len() }
+/// };
+/// assert_eq!(f.call(1), 11);
+/// assert_eq!(f.call("abc"), 3);
+/// drop(f);
+/// assert_eq!(log, ["int 1", "str abc"]);
+/// ```
+#[macro_export]
+macro_rules! ctx_poly {
+    (@cases [$($lt:lifetime),*] [$($field:ident),*]) => {};
+    (
+        @cases [$($lt:lifetime),*] [$($field:ident),*]
+        |$arg:ident: $in:ty| -> $out:ty $body:block $(,)? $($rest:tt)*
+    ) => {
+        impl<$($lt),*> $crate::Func<$in> for CtxPoly<$($lt),*> {
+            type Output = $out;
+
+            #[allow(unused_variables)]
+            fn call(&mut self, $arg: $in) -> $out {
+                let CtxPoly { $($field),* } = self;
+                $body
+            }
+        }
+
+        $crate::ctx_poly!(@cases [$($lt),*] [$($field),*] $($rest)*);
+    };
+    (
+        $(<$($lt:lifetime),+ $(,)?>)? { $($field:ident: $ty:ty = $init:expr),* $(,)? }
+        $($cases:tt)*
+    ) => {{
+        struct CtxPoly<$($($lt),+)?> {
+            $($field: $ty,)*
+        }
+
+        $crate::ctx_poly!(@cases [$($($lt),+)?] [$($field),*] $($cases)*);
+
+        CtxPoly { $($field: $init),* }
+    }};
+}
+
 impl<F: Func<Head>, Head, Tail: HMappable<Poly<F>>> HMappable<Poly<F>> for HCons<Head, Tail> {
     type Output = HCons<<F as Func<Head>>::Output, <Tail as HMappable<Poly<F>>>::Output>;
 
@@ -43,11 +247,26 @@ impl<F: Func<Head>, Head, Tail: HMappable<Poly<F>>> HMappable<Poly<F>> for HCons
 
 /// Convenience functions for the caller to map between similarly-shaped types implementing [Generic] without having to
 /// explicitly call [Generic::from] and [Generic::into]
+///
+/// frunk implements [Generic] for tuples of up to 24 elements, so these work on tuples as well as on named structs:
+///
+/// ```
+/// use frunk_utils::{DebugFunc, FieldCount, WithGeneric};
+///
+/// let shown: (String, String) = (1, 'x').hmap(DebugFunc);
+/// assert_eq!(shown, ("1".to_string(), "'x'".to_string()));
+/// assert_eq!(<(u8, u16, u32)>::FIELD_COUNT, 3);
+/// ```
 pub trait WithGeneric: Generic {
     fn hmap<U: Generic, F>(self, f: F) -> U
     where
         Self::Repr: HMappable<Poly<F>, Output = U::Repr>;
 
+    /// Like [hmap](WithGeneric::hmap) but short-circuits on the first field for which `f` returns an error
+    fn try_hmap<U: Generic, F, E>(self, f: F) -> Result<U, E>
+    where
+        Self::Repr: TryHMap<F, E, Output = U::Repr>;
+
     fn hzip<U: Generic, TU: Generic<Repr = <Self::Repr as HZippable<U::Repr>>::Zipped>>(
         self,
         other: U,
@@ -55,18 +274,252 @@ pub trait WithGeneric: Generic {
     where
         Self::Repr: HZippable<U::Repr>;
 
+    /// Zip three similarly-shaped structs into a struct of triples
+    fn hzip3<
+        U: Generic,
+        V: Generic,
+        TUV: Generic<Repr = <Self::Repr as HZip3<U::Repr, V::Repr>>::Zipped>,
+    >(
+        self,
+        u: U,
+        v: V,
+    ) -> TUV
+    where
+        Self::Repr: HZip3<U::Repr, V::Repr>;
+
+    /// The inverse of [hzip](WithGeneric::hzip): split a struct whose fields are all pairs into two structs
+    fn hunzip<U: Generic, V: Generic>(self) -> (U, V)
+    where
+        Self::Repr: HUnzip<Left = U::Repr, Right = V::Repr>;
+
+    /// Combine `self` and `other` field-wise with `f` in a single pass. Equivalent to [hzip](WithGeneric::hzip)
+    /// followed by [hmap](WithGeneric::hmap) but without the intermediate struct of tuples.
+    fn hmap2<U: Generic, V: Generic, F>(self, other: U, f: F) -> V
+    where
+        Self::Repr: HMap2<U::Repr, F, Output = V::Repr>;
+
+    /// Combine two values of the same struct, with `f` deciding field by field what to keep from `self` (its first
+    /// argument) and `other` (its second). [Coalesce] does this for structs of `Option`s.
+    fn merge_with<F>(self, other: Self, f: F) -> Self
+    where
+        Self::Repr: HMap2<Self::Repr, F, Output = Self::Repr>;
+
+    /// Combine three structs field-wise with `f` in a single pass
+    fn hmap3<U: Generic, V: Generic, W: Generic, F>(self, u: U, v: V, f: F) -> W
+    where
+        Self::Repr: HMap3<U::Repr, V::Repr, F, Output = W::Repr>;
+
     fn map_to_list<F, U>(self, f: F) -> ConsList<U, <Self::Repr as MapToList<F, U>>::Output>
     where
         Self::Repr: MapToList<F, U>;
 
+    /// Like [map_to_list](WithGeneric::map_to_list) but produces a plain array. `N` must equal the number of fields,
+    /// which is checked at compile time.
+    fn map_to_array<F, U, const N: usize>(self, f: F) -> [U; N]
+    where
+        Self::Repr: MapToList<F, U>;
+
+    /// Like [map_to_list](WithGeneric::map_to_list) but short-circuits on the first field for which `f` returns an
+    /// error. Any values already produced for earlier fields are dropped.
+    #[allow(clippy::type_complexity)]
+    fn try_map_to_list<F, U, E>(
+        self,
+        f: F,
+    ) -> Result<ConsList<U, <Self::Repr as TryMapToList<F, U, E>>::Output>, E>
+    where
+        Self::Repr: TryMapToList<F, U, E>;
+
     fn for_each<F>(self, f: F)
     where
         Self::Repr: ForEach<F>;
 
+    /// Like [for_each](WithGeneric::for_each) but visits the fields from last to first, e.g. to release resources in
+    /// the reverse of the order they were declared
+    fn for_each_rev<F>(self, f: F)
+    where
+        Self::Repr: ForEachRev<F>;
+
+    /// Check whether `f` returns true for every field, stopping at the first field for which it returns false
+    fn all<F>(self, f: F) -> bool
+    where
+        Self::Repr: All<F>;
+
+    /// Check whether `f` returns true for any field, stopping at the first field for which it does
+    fn any<F>(self, f: F) -> bool
+    where
+        Self::Repr: Any<F>;
+
+    /// Return the first `Some` produced by `f`, without visiting the fields after it
+    fn find_map<U, F>(self, f: F) -> Option<U>
+    where
+        Self::Repr: FindMap<F, U>;
+
+    /// Return the zero-based position of the first field for which `f` returns true
+    fn position<F>(self, f: F) -> Option<usize>
+    where
+        Self::Repr: Position<F>;
+
+    /// Map every field to a common type and combine the results from first to last with `reducer`, without collecting
+    /// them into a list first. Returns `None` if the struct has no fields.
+    fn map_reduce<U, F, R: FnMut(U, U) -> U>(self, f: F, reducer: R) -> Option<U>
+    where
+        Self::Repr: MapReduce<F, U>;
+
+    /// Collect every field whose type is exactly `U`, skipping the rest. Stable Rust can't tell two generic types
+    /// apart at the type level, so each field is checked at runtime by downcasting through [Any](std::any::Any). That
+    /// means `U` and every field type must be `'static`: structs with borrowed fields aren't supported.
+    fn fields_of_type<U: 'static>(self) -> Vec<U>
+    where
+        Self::Repr: HFold<OfType<U>, Vec<U>>;
+
+    /// Replace the one field of type `U` with the result of calling `f` on it, leaving every other field untouched.
+    /// As with frunk's `Plucker`, `Index` is inferred and the call is ambiguous if more than one field has type `U`.
+    fn replace_field<U, F, Index>(self, f: F) -> Self
+    where
+        Self::Repr: ReplaceField<U, Index>,
+        F: Func<U, Output = U>;
+
+    /// Turn a struct whose fields are all `Option`s into a struct of the contained values, or `None` if any field is
+    /// `None`
+    fn transpose_options<U: Generic>(self) -> Option<U>
+    where
+        Self::Repr: TransposeOptions<Output = U::Repr>;
+
+    /// Turn a struct whose fields are all `Result`s into a struct of the `Ok` values, or the first error
+    fn transpose_results<U: Generic, E>(self) -> Result<U, E>
+    where
+        Self::Repr: TryHMap<UnwrapResult, E, Output = U::Repr>;
+
+    /// Like [transpose_results](WithGeneric::transpose_results) but reports every error along with the position of
+    /// the field it came from
+    fn transpose_results_all<U: Generic, E>(self) -> Result<U, Vec<(usize, E)>>
+    where
+        Self::Repr: TransposeResultsAll<E, Output = U::Repr>;
+
+    /// Turn a struct whose fields are all iterators into an iterator of structs, stopping as soon as any field's
+    /// iterator is exhausted
+    fn zip_fields<U: Generic>(self) -> impl Iterator<Item = U>
+    where
+        Self::Repr: ZipIterators<Item = U::Repr>;
+
+    /// Like [hmap](WithGeneric::hmap) but runs `f` on every field on its own scoped thread, giving each field its own
+    /// clone of `f`. Needs no async runtime or thread pool. If `f` panics for any field, waits for the other threads
+    /// and then resumes the panic of the first such field in declaration order.
+    fn hmap_scoped_threads<'env, U: Generic, F>(self, f: F) -> U
+    where
+        Self::Repr: parallel::ScopedMap<'env, F, Output = U::Repr>;
+
+    /// Like [hmap](WithGeneric::hmap) but runs `f` on the fields in parallel on the rayon thread pool, giving each
+    /// field its own clone of `f`
+    #[cfg(feature = "rayon")]
+    fn par_hmap_rayon<U: Generic, F>(self, f: F) -> U
+    where
+        Self::Repr: parallel::RayonMap<F, Output = U::Repr>;
+
+    /// Like [for_each](WithGeneric::for_each) but runs `f` on the fields in parallel on the rayon thread pool, giving
+    /// each field its own clone of `f`
+    #[cfg(feature = "rayon")]
+    fn par_for_each_rayon<F>(self, f: F)
+    where
+        Self::Repr: parallel::RayonMap<F>;
+
+    /// Fold over the fields of the struct from first to last
+    fn hfold<Acc, F>(self, init: Acc, f: F) -> Acc
+    where
+        Self::Repr: HFold<F, Acc>;
+
+    /// Like [hmap](WithGeneric::hmap) but with an accumulator threaded through the fields in order, as with
+    /// [Iterator::scan]. The final accumulator is returned alongside the mapped struct.
+    fn map_accum<U: Generic, Acc, F>(self, init: Acc, f: F) -> (U, Acc)
+    where
+        Self::Repr: MapAccum<F, Acc, Output = U::Repr>;
+
+    /// Like [hmap](WithGeneric::hmap) but `f` is called with the zero-based position of each field along with its value
+    fn hmap_enumerate<U: Generic, F>(self, f: F) -> U
+    where
+        Self::Repr: MapAccum<Enumerate<F>, usize, Output = U::Repr>;
+
+    /// Like [for_each](WithGeneric::for_each) but `f` is called with the zero-based position of each field along with
+    /// its value
+    fn for_each_enumerate<F>(self, f: F)
+    where
+        Self::Repr: MapAccum<Enumerate<F>, usize>;
+
+    /// Like [for_each](WithGeneric::for_each) but stops visiting fields as soon as `f` returns an error
+    fn try_for_each<F, E>(self, f: F) -> Result<(), E>
+    where
+        Self::Repr: TryForEach<F, E>;
+
     /// Allows getting an iterator over the fields of a struct if they all have the same type
     fn fields_into_iter<U>(self) -> impl Iterator<Item = U>
     where
         Self::Repr: MapToList<Identity, U>;
+
+    /// The inverse of [fields_into_iter](WithGeneric::fields_into_iter): build a struct whose fields all have the same
+    /// type from exactly [FIELD_COUNT](FieldCount::FIELD_COUNT) items
+    fn from_fields_iter<U, I: IntoIterator<Item = U>>(iter: I) -> Result<Self, FieldCountMismatch>
+    where
+        Self: Sized,
+        Self::Repr: TakeFromIter<U>;
+
+    /// Like [from_fields_iter](WithGeneric::from_fields_iter) but only takes the items it needs, leaving any others in
+    /// `iter`
+    fn from_fields_iter_prefix<U, I: Iterator<Item = U>>(
+        iter: &mut I,
+    ) -> Result<Self, FieldCountMismatch>
+    where
+        Self: Sized,
+        Self::Repr: TakeFromIter<U>;
+
+    /// Fill a struct from the columns of a CSV record by position, converting each cell with `f`. The record must have
+    /// exactly one column per field.
+    #[cfg(feature = "csv")]
+    fn from_csv_record<F, E>(
+        record: &::csv::StringRecord,
+        f: F,
+    ) -> Result<Self, csv::RecordError<E>>
+    where
+        Self: Sized,
+        Self::Repr: csv::FromRecord<F, E>;
+
+    /// Build a struct whose fields all have the same type by calling `f` with the index of each field, like
+    /// [array::from_fn](std::array::from_fn)
+    fn from_fields_fn<U, F: FnMut(usize) -> U>(f: F) -> Self
+    where
+        Self: Sized,
+        Self::Repr: TakeFromIter<U>;
+
+    /// Like [for_each](WithGeneric::for_each) for a struct whose fields all have the same type. The fields are first
+    /// moved into a [ConsList], which still recurses once per field, but `f` is then called from a single loop over
+    /// it rather than being inlined at each level of the recursion.
+    fn for_each_uniform<U, F: FnMut(U)>(self, f: F)
+    where
+        Self::Repr: MapToList<Identity, U>;
+
+    /// Like [hmap](WithGeneric::hmap) for a struct whose fields all have the same type, mapping them with a single
+    /// closure into a struct whose fields all have the output type. As with
+    /// [for_each_uniform](WithGeneric::for_each_uniform), the fields are moved in and out of a [ConsList] recursively
+    /// but `f` is called from a loop with [ConsList::map].
+    fn hmap_uniform<V: Generic, U, W, F: FnMut(U) -> W>(self, f: F) -> V
+    where
+        Self::Repr: MapToList<Identity, U>,
+        <Self::Repr as MapToList<Identity, U>>::Output:
+            cons_list::MapElems<U, W, Output = <V::Repr as FromList<W>>::List>,
+        V::Repr: FromList<W>;
+
+    /// Construct a struct by calling `f` once per field, in declaration order, with a [PhantomData] token for the
+    /// field's type
+    fn build_with<F>(f: F) -> Self
+    where
+        Self: Sized,
+        Self::Repr: Build<F>;
+
+    /// A [Builder](builder::Builder) with none of the fields supplied, which can only be built once all of them have
+    /// been
+    fn builder() -> builder::Builder<Self, <Self::Repr as builder::Unfilled>::Slots>
+    where
+        Self: Sized,
+        Self::Repr: builder::Unfilled;
 }
 
 pub struct Identity;
@@ -79,6 +532,122 @@ impl<T> Func<T> for Identity {
     }
 }
 
+/// Produces a clone of the contained value for every field
+pub struct ConstFunc<T>(pub T);
+
+impl<T: Clone, I> Func<I> for ConstFunc<T> {
+    type Output = T;
+
+    fn call(&mut self, _i: I) -> Self::Output {
+        self.0.clone()
+    }
+}
+
+/// Drops every field
+pub struct DropFunc;
+
+impl<T> Func<T> for DropFunc {
+    type Output = ();
+
+    fn call(&mut self, i: T) -> Self::Output {
+        drop(i)
+    }
+}
+
+/// Clones every field out of a traversal by reference
+pub struct CloneFunc;
+
+impl<T: Clone> Func<&T> for CloneFunc {
+    type Output = T;
+
+    fn call(&mut self, i: &T) -> Self::Output {
+        i.clone()
+    }
+}
+
+/// Adapts a [Func] that takes `(position, value)` pairs into a [Scanner] that counts the fields as it goes
+pub struct Enumerate<F>(pub F);
+
+impl<F: Func<(usize, I)>, I> Scanner<usize, I> for Enumerate<F> {
+    type Output = F::Output;
+
+    fn scan(&mut self, acc: usize, i: I) -> (usize, Self::Output) {
+        (acc + 1, self.0.call((acc, i)))
+    }
+}
+
+/// A [Folder] that collects the fields of type `T` and skips all others, comparing types with a runtime
+/// [Any](std::any::Any) downcast
+pub struct OfType<T>(PhantomData<fn() -> T>);
+
+impl<T: 'static, I: 'static> Folder<Vec<T>, I> for OfType<T> {
+    fn fold(&mut self, mut acc: Vec<T>, i: I) -> Vec<T> {
+        if let Some(i) = (&mut Some(i) as &mut dyn std::any::Any).downcast_mut::<Option<T>>() {
+            acc.extend(i.take());
+        }
+        acc
+    }
+}
+
+/// A [Folder] over references that collects the ones pointing to a `T` and skips all others, comparing types with a
+/// runtime [Any](std::any::Any) downcast
+pub struct RefsOfType<T>(PhantomData<fn() -> T>);
+
+impl<'a, T: 'static, I: 'static> Folder<Vec<&'a T>, &'a I> for RefsOfType<T> {
+    fn fold(&mut self, mut acc: Vec<&'a T>, i: &'a I) -> Vec<&'a T> {
+        acc.extend((i as &dyn std::any::Any).downcast_ref::<T>());
+        acc
+    }
+}
+
+/// A [TryFunc] that unwraps `Ok` values and passes errors through
+pub struct UnwrapResult;
+
+impl<T, E> TryFunc<Result<T, E>> for UnwrapResult {
+    type Output = T;
+    type Error = E;
+
+    fn try_call(&mut self, i: Result<T, E>) -> Result<T, E> {
+        i
+    }
+}
+
+/// A [Func2] over `Option`s that keeps the second value if it's present and falls back to the first
+pub struct Coalesce;
+
+impl<T> Func2<Option<T>, Option<T>> for Coalesce {
+    type Output = Option<T>;
+
+    fn call(&mut self, a: Option<T>, b: Option<T>) -> Self::Output {
+        b.or(a)
+    }
+}
+
+/// Formats every field with its [Debug](std::fmt::Debug) impl
+pub struct DebugFunc;
+
+impl<T: std::fmt::Debug> Func<T> for DebugFunc {
+    type Output = String;
+
+    fn call(&mut self, i: T) -> Self::Output {
+        format!("{i:?}")
+    }
+}
+
+/// The number of fields in a struct implementing [Generic]. This is kept out of [WithGeneric] so that its blanket impl
+/// doesn't need to require an [HList] representation.
+pub trait FieldCount: Generic {
+    /// The number of fields in the struct
+    const FIELD_COUNT: usize;
+}
+
+impl<T: Generic> FieldCount for T
+where
+    T::Repr: HList,
+{
+    const FIELD_COUNT: usize = T::Repr::LEN;
+}
+
 impl<T: Generic> WithGeneric for T {
     fn hmap<U: Generic, F>(self, f: F) -> U
     where
@@ -87,6 +656,13 @@ impl<T: Generic> WithGeneric for T {
         Generic::from(Generic::into(self).map(Poly(f)))
     }
 
+    fn try_hmap<U: Generic, F, E>(self, f: F) -> Result<U, E>
+    where
+        Self::Repr: TryHMap<F, E, Output = U::Repr>,
+    {
+        Generic::into(self).try_hmap(f).map(Generic::from)
+    }
+
     fn hzip<U: Generic, TU: Generic<Repr = <Self::Repr as HZippable<U::Repr>>::Zipped>>(
         self,
         other: U,
@@ -97,62 +673,427 @@ impl<T: Generic> WithGeneric for T {
         Generic::from(Generic::into(self).zip(Generic::into(other)))
     }
 
-    fn map_to_list<F, U>(self, f: F) -> ConsList<U, <Self::Repr as MapToList<F, U>>::Output>
+    fn hzip3<
+        U: Generic,
+        V: Generic,
+        TUV: Generic<Repr = <Self::Repr as HZip3<U::Repr, V::Repr>>::Zipped>,
+    >(
+        self,
+        u: U,
+        v: V,
+    ) -> TUV
     where
-        Self::Repr: MapToList<F, U>,
+        Self::Repr: HZip3<U::Repr, V::Repr>,
     {
-        Generic::into(self).map_to_list(f)
+        Generic::from(Generic::into(self).hzip3(Generic::into(u), Generic::into(v)))
     }
 
-    fn for_each<F>(self, f: F)
+    fn hunzip<U: Generic, V: Generic>(self) -> (U, V)
     where
-        Self::Repr: ForEach<F>,
+        Self::Repr: HUnzip<Left = U::Repr, Right = V::Repr>,
     {
-        Generic::into(self).for_each(f)
+        let (left, right) = Generic::into(self).hunzip();
+        (Generic::from(left), Generic::from(right))
     }
 
-    fn fields_into_iter<U>(self) -> impl Iterator<Item = U>
+    fn hmap2<U: Generic, V: Generic, F>(self, other: U, f: F) -> V
     where
-        Self::Repr: MapToList<Identity, U>,
+        Self::Repr: HMap2<U::Repr, F, Output = V::Repr>,
     {
-        self.map_to_list(Identity).into_iter()
+        Generic::from(Generic::into(self).hmap2(Generic::into(other), f))
     }
-}
 
-/// Convenience functions for the caller to map between similarly-shaped types implementing [LabelledGeneric] without
-/// having to explicitly call [LabelledGeneric::from] and [LabelledGeneric::into]
-pub trait WithLabelledGeneric: LabelledGeneric {
-    fn hmap<U: LabelledGeneric, F>(self, f: F) -> U
+    fn merge_with<F>(self, other: Self, f: F) -> Self
     where
-        Self::Repr: HMappable<Poly<F>, Output = U::Repr>;
+        Self::Repr: HMap2<Self::Repr, F, Output = Self::Repr>,
+    {
+        self.hmap2(other, f)
+    }
 
-    fn hzip<
-        U: LabelledGeneric,
-        TU: LabelledGeneric<Repr = <Self::Repr as HZippable<U::Repr>>::Zipped>,
-    >(
-        self,
-        other: U,
-    ) -> TU
+    fn hmap3<U: Generic, V: Generic, W: Generic, F>(self, u: U, v: V, f: F) -> W
     where
-        Self::Repr: HZippable<U::Repr>;
+        Self::Repr: HMap3<U::Repr, V::Repr, F, Output = W::Repr>,
+    {
+        Generic::from(Generic::into(self).hmap3(Generic::into(u), Generic::into(v), f))
+    }
 
     fn map_to_list<F, U>(self, f: F) -> ConsList<U, <Self::Repr as MapToList<F, U>>::Output>
     where
-        Self::Repr: MapToList<F, U>;
-
-    fn for_each<F>(self, f: F)
-    where
-        Self::Repr: ForEach<F>;
-}
+        Self::Repr: MapToList<F, U>,
+    {
+        Generic::into(self).map_to_list(f)
+    }
 
-impl<T: LabelledGeneric> WithLabelledGeneric for T {
-    fn hmap<U: LabelledGeneric, F>(self, f: F) -> U
+    fn map_to_array<F, U, const N: usize>(self, f: F) -> [U; N]
     where
-        Self::Repr: HMappable<Poly<F>, Output = U::Repr>,
+        Self::Repr: MapToList<F, U>,
     {
-        LabelledGeneric::from(LabelledGeneric::into(self).map(Poly(f)))
+        Generic::into(self).map_to_array(f)
     }
 
+    fn try_map_to_list<F, U, E>(
+        self,
+        f: F,
+    ) -> Result<ConsList<U, <Self::Repr as TryMapToList<F, U, E>>::Output>, E>
+    where
+        Self::Repr: TryMapToList<F, U, E>,
+    {
+        Generic::into(self).try_map_to_list(f)
+    }
+
+    fn for_each<F>(self, f: F)
+    where
+        Self::Repr: ForEach<F>,
+    {
+        Generic::into(self).for_each(f)
+    }
+
+    fn for_each_rev<F>(self, mut f: F)
+    where
+        Self::Repr: ForEachRev<F>,
+    {
+        Generic::into(self).for_each_rev(&mut f)
+    }
+
+    fn all<F>(self, f: F) -> bool
+    where
+        Self::Repr: All<F>,
+    {
+        Generic::into(self).all(f)
+    }
+
+    fn any<F>(self, f: F) -> bool
+    where
+        Self::Repr: Any<F>,
+    {
+        Generic::into(self).any(f)
+    }
+
+    fn find_map<U, F>(self, f: F) -> Option<U>
+    where
+        Self::Repr: FindMap<F, U>,
+    {
+        Generic::into(self).find_map(f)
+    }
+
+    fn position<F>(self, f: F) -> Option<usize>
+    where
+        Self::Repr: Position<F>,
+    {
+        Generic::into(self).position(f)
+    }
+
+    fn map_reduce<U, F, R: FnMut(U, U) -> U>(self, f: F, reducer: R) -> Option<U>
+    where
+        Self::Repr: MapReduce<F, U>,
+    {
+        Generic::into(self).map_reduce(f, reducer)
+    }
+
+    fn fields_of_type<U: 'static>(self) -> Vec<U>
+    where
+        Self::Repr: HFold<OfType<U>, Vec<U>>,
+    {
+        self.hfold(Vec::new(), OfType(PhantomData))
+    }
+
+    fn replace_field<U, F, Index>(self, f: F) -> Self
+    where
+        Self::Repr: ReplaceField<U, Index>,
+        F: Func<U, Output = U>,
+    {
+        Generic::from(Generic::into(self).replace_field(f))
+    }
+
+    fn transpose_options<U: Generic>(self) -> Option<U>
+    where
+        Self::Repr: TransposeOptions<Output = U::Repr>,
+    {
+        Generic::into(self).transpose_options().map(Generic::from)
+    }
+
+    fn transpose_results<U: Generic, E>(self) -> Result<U, E>
+    where
+        Self::Repr: TryHMap<UnwrapResult, E, Output = U::Repr>,
+    {
+        self.try_hmap(UnwrapResult)
+    }
+
+    fn transpose_results_all<U: Generic, E>(self) -> Result<U, Vec<(usize, E)>>
+    where
+        Self::Repr: TransposeResultsAll<E, Output = U::Repr>,
+    {
+        let mut errors = Vec::new();
+        match Generic::into(self).transpose_results_all(0, &mut errors) {
+            Some(repr) => Ok(Generic::from(repr)),
+            None => Err(errors),
+        }
+    }
+
+    fn zip_fields<U: Generic>(self) -> impl Iterator<Item = U>
+    where
+        Self::Repr: ZipIterators<Item = U::Repr>,
+    {
+        let mut iters = Generic::into(self);
+        std::iter::from_fn(move || iters.next_fields().map(Generic::from))
+    }
+
+    fn hmap_scoped_threads<'env, U: Generic, F>(self, f: F) -> U
+    where
+        Self::Repr: parallel::ScopedMap<'env, F, Output = U::Repr>,
+    {
+        std::thread::scope(|scope| parallel::ScopedMap::scoped_map(Generic::into(self), f, scope))
+            .map(Generic::from)
+            .unwrap_or_else(|payload| std::panic::resume_unwind(payload))
+    }
+
+    #[cfg(feature = "rayon")]
+    fn par_hmap_rayon<U: Generic, F>(self, f: F) -> U
+    where
+        Self::Repr: parallel::RayonMap<F, Output = U::Repr>,
+    {
+        Generic::from(parallel::RayonMap::rayon_map(Generic::into(self), f))
+    }
+
+    #[cfg(feature = "rayon")]
+    fn par_for_each_rayon<F>(self, f: F)
+    where
+        Self::Repr: parallel::RayonMap<F>,
+    {
+        parallel::RayonMap::rayon_map(Generic::into(self), f);
+    }
+
+    fn hfold<Acc, F>(self, init: Acc, f: F) -> Acc
+    where
+        Self::Repr: HFold<F, Acc>,
+    {
+        Generic::into(self).hfold(init, f)
+    }
+
+    fn map_accum<U: Generic, Acc, F>(self, init: Acc, f: F) -> (U, Acc)
+    where
+        Self::Repr: MapAccum<F, Acc, Output = U::Repr>,
+    {
+        let (repr, acc) = Generic::into(self).map_accum(init, f);
+        (Generic::from(repr), acc)
+    }
+
+    fn hmap_enumerate<U: Generic, F>(self, f: F) -> U
+    where
+        Self::Repr: MapAccum<Enumerate<F>, usize, Output = U::Repr>,
+    {
+        self.map_accum(0, Enumerate(f)).0
+    }
+
+    fn for_each_enumerate<F>(self, f: F)
+    where
+        Self::Repr: MapAccum<Enumerate<F>, usize>,
+    {
+        Generic::into(self).map_accum(0, Enumerate(f));
+    }
+
+    fn try_for_each<F, E>(self, f: F) -> Result<(), E>
+    where
+        Self::Repr: TryForEach<F, E>,
+    {
+        Generic::into(self).try_for_each(f)
+    }
+
+    fn fields_into_iter<U>(self) -> impl Iterator<Item = U>
+    where
+        Self::Repr: MapToList<Identity, U>,
+    {
+        self.map_to_list(Identity).into_iter()
+    }
+
+    fn from_fields_iter<U, I: IntoIterator<Item = U>>(iter: I) -> Result<Self, FieldCountMismatch>
+    where
+        Self::Repr: TakeFromIter<U>,
+    {
+        let mut iter = iter.into_iter();
+        let result = Self::from_fields_iter_prefix(&mut iter)?;
+        if iter.next().is_some() {
+            return Err(FieldCountMismatch::TooMany {
+                expected: Self::Repr::LEN,
+            });
+        }
+        Ok(result)
+    }
+
+    fn from_fields_iter_prefix<U, I: Iterator<Item = U>>(
+        iter: &mut I,
+    ) -> Result<Self, FieldCountMismatch>
+    where
+        Self::Repr: TakeFromIter<U>,
+    {
+        TakeFromIter::take_from_iter(iter)
+            .map(Generic::from)
+            .map_err(|found| FieldCountMismatch::TooFew {
+                expected: Self::Repr::LEN,
+                found,
+            })
+    }
+
+    #[cfg(feature = "csv")]
+    fn from_csv_record<F, E>(
+        record: &::csv::StringRecord,
+        f: F,
+    ) -> Result<Self, csv::RecordError<E>>
+    where
+        Self::Repr: csv::FromRecord<F, E>,
+    {
+        if record.len() != Self::Repr::LEN {
+            return Err(csv::RecordError::Length {
+                expected: Self::Repr::LEN,
+                found: record.len(),
+            });
+        }
+        csv::FromRecord::from_record(record, 0, f).map(Generic::from)
+    }
+
+    fn from_fields_fn<U, F: FnMut(usize) -> U>(f: F) -> Self
+    where
+        Self::Repr: TakeFromIter<U>,
+    {
+        match Self::from_fields_iter_prefix(&mut (0..).map(f)) {
+            Ok(result) => result,
+            Err(_) => unreachable!("an unbounded iterator can't run out"),
+        }
+    }
+
+    fn for_each_uniform<U, F: FnMut(U)>(self, f: F)
+    where
+        Self::Repr: MapToList<Identity, U>,
+    {
+        self.fields_into_iter().for_each(f)
+    }
+
+    fn hmap_uniform<V: Generic, U, W, F: FnMut(U) -> W>(self, f: F) -> V
+    where
+        Self::Repr: MapToList<Identity, U>,
+        <Self::Repr as MapToList<Identity, U>>::Output:
+            cons_list::MapElems<U, W, Output = <V::Repr as FromList<W>>::List>,
+        V::Repr: FromList<W>,
+    {
+        Generic::from(FromList::from_list(
+            Generic::into(self).map_to_list(Identity).map(f),
+        ))
+    }
+
+    fn build_with<F>(f: F) -> Self
+    where
+        Self::Repr: Build<F>,
+    {
+        Generic::from(Build::build(f))
+    }
+
+    fn builder() -> builder::Builder<Self, <Self::Repr as builder::Unfilled>::Slots>
+    where
+        Self::Repr: builder::Unfilled,
+    {
+        builder::Builder::new()
+    }
+}
+
+/// Convenience functions for the caller to map between similarly-shaped types implementing [LabelledGeneric] without
+/// having to explicitly call [LabelledGeneric::from] and [LabelledGeneric::into]
+pub trait WithLabelledGeneric: LabelledGeneric {
+    fn hmap<U: LabelledGeneric, F>(self, f: F) -> U
+    where
+        Self::Repr: HMappable<Poly<F>, Output = U::Repr>;
+
+    /// Like [hmap](WithLabelledGeneric::hmap) but `f` is a [LabelledFunc] that also receives each field's name
+    fn hmap_named<U: LabelledGeneric, F>(self, f: F) -> U
+    where
+        Self::Repr: labelled::HMapNamed<F, Output = U::Repr>;
+
+    /// Construct a struct by calling `f` once per field, in declaration order, with the field's name and a
+    /// [PhantomData] token for its type
+    fn build_with_named<F>(f: F) -> Self
+    where
+        Self: Sized,
+        Self::Repr: labelled::BuildNamed<F>;
+
+    /// Fill a struct from a CSV record, converting the cell in the column whose header matches each field's name with
+    /// `f`. Columns that don't correspond to a field are ignored.
+    #[cfg(feature = "csv")]
+    fn from_csv_record_named<F, E>(
+        headers: &::csv::StringRecord,
+        record: &::csv::StringRecord,
+        f: F,
+    ) -> Result<Self, csv::RecordError<E>>
+    where
+        Self: Sized,
+        Self::Repr: csv::FromRecordNamed<F, E>;
+
+    /// Load a struct from the environment variables `PREFIX_FIELD`, with each field's name in upper case, parsing them
+    /// with [FromStr](std::str::FromStr). Every field is attempted, and all of the variables that are missing or invalid
+    /// are reported together.
+    fn from_env(prefix: &str) -> Result<Self, env::EnvError>
+    where
+        Self: Sized,
+        Self::Repr: env::FromEnv;
+
+    /// Like [from_env](WithLabelledGeneric::from_env) but reads each variable through `lookup` rather than from the
+    /// process environment, e.g. to load from a map or a `.env` file
+    fn from_env_with(
+        prefix: &str,
+        lookup: impl FnMut(&str) -> Result<String, std::env::VarError>,
+    ) -> Result<Self, env::EnvError>
+    where
+        Self: Sized,
+        Self::Repr: env::FromEnv;
+
+    /// Fill a struct from a database row or similar by calling `f` with the row, the name of each field and a token
+    /// for its type. [row::GetColumn] does this for rows implementing [row::RowGet]. Stops at the first field that
+    /// fails.
+    fn from_row<R: ?Sized, F, E>(row: &R, f: F) -> Result<Self, row::ColumnError<E>>
+    where
+        Self: Sized,
+        Self::Repr: row::FromRow<R, F, E>;
+
+    /// Parse a struct from a row of strings, one per field in declaration order, with each field's
+    /// [FromStr](std::str::FromStr) impl. Fails on the first missing or invalid value, or if the row has values left
+    /// over.
+    fn parse_fields<'s, I: IntoIterator<Item = &'s str>>(
+        values: I,
+    ) -> Result<Self, parse::ParseError>
+    where
+        Self: Sized,
+        Self::Repr: parse::ParseFields<'s>;
+
+    /// Start from the [Default] value of every field's type and pass each one through `f` along with the field's name
+    /// so it can be overridden. `Self` doesn't need to implement [Default] itself.
+    fn build_default_with<F>(f: F) -> Self
+    where
+        Self: Sized,
+        Self::Repr: partial::Optionals + labelled::HMapNamed<F, Output = Self::Repr>,
+        <Self::Repr as partial::Optionals>::Options:
+            partial::FillMissing<partial::DefaultValue, Output = Self::Repr>;
+
+    /// Map into a struct whose fields are a reordered subset of this struct's fields, matched up by name. `f` is applied
+    /// to the value of every field that survives, in the target's field order; the other fields are dropped.
+    fn hmap_sculpted<U: LabelledGeneric, F, Indices>(self, f: F) -> U
+    where
+        U::Repr: labelled::SculptMap<Self::Repr, F, Indices>;
+
+    /// Like frunk's `transmogrify`, recursively restructure into `U` by field name, but apply `f` to each leaf value
+    /// along the way
+    fn transmogrify_with<U, F, Indices>(self, f: F) -> U
+    where
+        Self: transmogrify::TransmogrifyWith<U, F, Indices>;
+
+    /// Like [WithGeneric::transpose_options] for labelled structs
+    fn transpose_options<U: LabelledGeneric>(self) -> Option<U>
+    where
+        Self::Repr: TransposeOptions<Output = U::Repr>;
+
+    /// Turn a struct whose fields are all `Option`s into a struct of the contained values, taking the [Default] value
+    /// for each field that is `None`
+    fn fill_defaults<U: LabelledGeneric>(self) -> U
+    where
+        Self::Repr: partial::FillMissing<partial::DefaultValue, Output = U::Repr>;
+
     fn hzip<
         U: LabelledGeneric,
         TU: LabelledGeneric<Repr = <Self::Repr as HZippable<U::Repr>>::Zipped>,
@@ -161,65 +1102,2170 @@ impl<T: LabelledGeneric> WithLabelledGeneric for T {
         other: U,
     ) -> TU
     where
-        Self::Repr: HZippable<U::Repr>,
+        Self::Repr: HZippable<U::Repr>;
+
+    /// Zip three structs with the same field names into a struct whose fields hold the triples of values
+    fn hzip3<
+        U: LabelledGeneric,
+        V: LabelledGeneric,
+        TUV: LabelledGeneric<Repr = <Self::Repr as labelled::LabelledHZip3<U::Repr, V::Repr>>::Zipped>,
+    >(
+        self,
+        u: U,
+        v: V,
+    ) -> TUV
+    where
+        Self::Repr: labelled::LabelledHZip3<U::Repr, V::Repr>;
+
+    fn map_to_list<F, U>(self, f: F) -> ConsList<U, <Self::Repr as MapToList<F, U>>::Output>
+    where
+        Self::Repr: MapToList<F, U>;
+
+    /// Map `f` over the field values and pair each result with the name of its field
+    #[allow(clippy::type_complexity)]
+    fn map_to_named_list<F, U>(
+        self,
+        f: F,
+    ) -> ConsList<
+        (&'static str, U),
+        <Self::Repr as MapToList<labelled::Named<F>, (&'static str, U)>>::Output,
+    >
+    where
+        Self::Repr: MapToList<labelled::Named<F>, (&'static str, U)>;
+
+    fn for_each<F>(self, f: F)
+    where
+        Self::Repr: ForEach<F>;
+
+    /// Like [for_each](WithLabelledGeneric::for_each) but `f` is a [LabelledFunc] that also receives each field's name
+    fn for_each_named<F>(self, f: F)
+    where
+        Self::Repr: labelled::ForEachNamed<F>;
+
+    /// Allows getting an iterator over the field values of a struct if they all have the same type
+    fn fields_into_iter<U>(self) -> impl Iterator<Item = U>
+    where
+        Self::Repr: MapToList<labelled::FieldValue, U>;
+
+    /// Collect the fields of a struct whose fields all have the same type into a map keyed by field name
+    fn to_hashmap<U>(self) -> HashMap<&'static str, U>
+    where
+        Self::Repr: MapToList<labelled::Named<Identity>, (&'static str, U)>;
+
+    /// Like [to_hashmap](WithLabelledGeneric::to_hashmap) but produces an ordered map
+    fn to_btreemap<U>(self) -> BTreeMap<&'static str, U>
+    where
+        Self::Repr: MapToList<labelled::Named<Identity>, (&'static str, U)>;
+
+    /// Iterate over the names of a struct's fields along with references to their values, in declaration order. The
+    /// fields must all have the same type.
+    fn iter_named<'a, U: 'a>(&'a self) -> impl Iterator<Item = (&'static str, &'a U)>
+    where
+        &'a Self: IntoLabelledGeneric,
+        <&'a Self as IntoLabelledGeneric>::Repr:
+            MapToList<labelled::Named<Identity>, (&'static str, &'a U)>;
+
+    /// The names of the fields whose values differ between `self` and `other`, in declaration order
+    fn diff<'a>(&'a self, other: &'a Self) -> Vec<&'static str>
+    where
+        &'a Self: IntoLabelledGeneric,
+        <&'a Self as IntoLabelledGeneric>::Repr: partial::Diff;
+
+    /// A [Partial](partial::Partial) holding the values of `other` that differ from `self`, so that applying it to
+    /// `self` produces `other`
+    fn diff_patch<'a>(&'a self, other: Self) -> partial::Partial<Self>
+    where
+        Self: Sized,
+        Self::Repr: partial::Optionals,
+        &'a Self: IntoLabelledGeneric,
+        <&'a Self as IntoLabelledGeneric>::Repr:
+            partial::DiffPatch<Self::Repr, Options = <Self::Repr as partial::Optionals>::Options>;
+
+    /// Build a struct whose fields all have the same type by removing each field's value from `map`. Fails if any
+    /// field is missing; entries that don't correspond to a field are ignored.
+    fn from_hashmap<U, K: Borrow<str> + Hash + Eq, S: BuildHasher>(
+        map: HashMap<K, U, S>,
+    ) -> Result<Self, labelled::MissingField>
+    where
+        Self: Sized,
+        Self::Repr: labelled::TakeNamed<U>;
+
+    /// The names of the struct's fields in declaration order, computed at compile time
+    fn field_names() -> ConsList<&'static str, <Self::Repr as field_names::FieldNames>::Names>
+    where
+        Self::Repr: field_names::FieldNames;
+
+    /// Map the field values into the corresponding fields of `U` using `f`, collecting the name and error of every
+    /// field that fails rather than stopping at the first one
+    fn validate<U: LabelledGeneric, F, E>(self, f: F) -> Result<U, validate::FieldErrors<E>>
+    where
+        Self::Repr: validate::Validate<F, E, Output = U::Repr>;
+
+    /// Like [hmap](WithLabelledGeneric::hmap) for a `f` that maps each field value into a future. The futures are all
+    /// awaited concurrently within the current task and their outputs collected into the fields of `U`.
+    #[cfg(feature = "futures")]
+    fn hmap_async<U: LabelledGeneric, F>(self, f: F) -> impl std::future::Future<Output = U>
+    where
+        Self::Repr: future::HMapNamedAsync<labelled::IgnoreName<F>, Output = U::Repr>;
+
+    /// Like [hmap_async](WithLabelledGeneric::hmap_async) but awaits each field's future before calling `f` on the
+    /// next field
+    #[cfg(feature = "futures")]
+    fn hmap_async_sequential<U: LabelledGeneric, F>(
+        self,
+        f: F,
+    ) -> impl std::future::Future<Output = U>
+    where
+        Self::Repr: future::HMapNamedAsync<labelled::IgnoreName<F>, Output = U::Repr>;
+
+    /// Like [hmap_async](WithLabelledGeneric::hmap_async) but `f` is a [LabelledFunc] that also receives each field's
+    /// name
+    #[cfg(feature = "futures")]
+    fn hmap_named_async<U: LabelledGeneric, F>(self, f: F) -> impl std::future::Future<Output = U>
+    where
+        Self::Repr: future::HMapNamedAsync<F, Output = U::Repr>;
+
+    /// Like [hmap_async](WithLabelledGeneric::hmap_async) for a `f` whose futures are only awaited for their side
+    /// effects
+    #[cfg(feature = "futures")]
+    fn for_each_async<F>(self, f: F) -> impl std::future::Future<Output = ()>
+    where
+        Self::Repr: future::HMapNamedAsync<labelled::IgnoreName<F>>;
+
+    /// Like [for_each_async](WithLabelledGeneric::for_each_async) but awaits each field's future before calling `f` on
+    /// the next field
+    #[cfg(feature = "futures")]
+    fn for_each_async_sequential<F>(self, f: F) -> impl std::future::Future<Output = ()>
+    where
+        Self::Repr: future::HMapNamedAsync<labelled::IgnoreName<F>>;
+
+    /// Like [map_to_list](WithLabelledGeneric::map_to_list) for a `f` that maps each field value into a future with a
+    /// common output type, awaiting them all concurrently within the current task
+    #[cfg(feature = "futures")]
+    #[allow(clippy::type_complexity)]
+    fn map_to_list_async<F, U>(
+        self,
+        f: F,
+    ) -> impl std::future::Future<
+        Output = ConsList<
+            U,
+            <<Self::Repr as future::HMapNamedAsync<labelled::IgnoreName<F>>>::Output as MapToList<
+                labelled::FieldValue,
+                U,
+            >>::Output,
+        >,
+    >
+    where
+        Self::Repr: future::HMapNamedAsync<labelled::IgnoreName<F>>,
+        <Self::Repr as future::HMapNamedAsync<labelled::IgnoreName<F>>>::Output:
+            MapToList<labelled::FieldValue, U>;
+
+    /// Like [validate](WithLabelledGeneric::validate) for a `f` that maps each field into a future producing a
+    /// `Result`. The futures are all awaited concurrently within the current task.
+    #[cfg(feature = "futures")]
+    fn validate_async<U: LabelledGeneric, F, E>(
+        self,
+        f: F,
+    ) -> impl std::future::Future<Output = Result<U, validate::FieldErrors<E>>>
+    where
+        Self::Repr: future::ValidateAsync<F, E, Output = U::Repr>;
+
+    /// Map every field into a future with `f` and await them all concurrently within the current task, with each
+    /// future running inside a `tracing` span recording the name of its field. See [future::Traced].
+    #[cfg(feature = "tracing")]
+    fn hmap_async_traced<U: LabelledGeneric, F>(self, f: F) -> impl std::future::Future<Output = U>
+    where
+        Self::Repr: future::HMapNamedAsync<future::Traced<F>, Output = U::Repr>;
+
+    /// Like [hmap_async_traced](WithLabelledGeneric::hmap_async_traced) for a `f` whose futures are only awaited for
+    /// their side effects
+    #[cfg(feature = "tracing")]
+    fn for_each_async_traced<F>(self, f: F) -> impl std::future::Future<Output = ()>
+    where
+        Self::Repr: future::HMapNamedAsync<future::Traced<F>>;
+
+    /// Like [WithGeneric::transpose_results] for labelled structs, reporting the name of the field that failed
+    fn transpose_results<U: LabelledGeneric, E>(self) -> Result<U, (&'static str, E)>
+    where
+        Self::Repr: TryHMap<labelled::UnwrapNamedResult, (&'static str, E), Output = U::Repr>;
+
+    /// Like [transpose_results](WithLabelledGeneric::transpose_results) but reports every failed field
+    fn transpose_results_all<U: LabelledGeneric, E>(self) -> Result<U, validate::FieldErrors<E>>
+    where
+        Self::Repr: validate::Validate<UnwrapResult, E, Output = U::Repr>;
+
+    /// Lock every field of a struct of [Mutex](std::sync::Mutex)es in declaration order, producing a struct of guards
+    /// with the same field names. Fails with the name of the first field whose lock is poisoned.
+    fn lock_all<'a, U: LabelledGeneric>(&'a self) -> Result<U, sync::Poisoned>
+    where
+        &'a Self: IntoLabelledGeneric,
+        <&'a Self as IntoLabelledGeneric>::Repr: sync::LockAll<'a, Guards = U::Repr>;
+
+    /// Like [lock_all](WithLabelledGeneric::lock_all) for a struct of [tokio::sync::Mutex]es
+    #[cfg(feature = "tokio")]
+    fn lock_all_async<'a, U: LabelledGeneric>(&'a self) -> impl std::future::Future<Output = U>
+    where
+        &'a Self: IntoLabelledGeneric,
+        <&'a Self as IntoLabelledGeneric>::Repr: sync::LockAllAsync<'a, Guards = U::Repr>;
+
+    /// Acquire shared access to every field of a struct of [RwLock](std::sync::RwLock)s in declaration order,
+    /// producing a struct of read guards with the same field names
+    fn read_all<'a, U: LabelledGeneric>(&'a self) -> Result<U, sync::Poisoned>
+    where
+        &'a Self: IntoLabelledGeneric,
+        <&'a Self as IntoLabelledGeneric>::Repr: sync::LockAllRw<'a, ReadGuards = U::Repr>;
+
+    /// Like [read_all](WithLabelledGeneric::read_all) but acquires exclusive access, producing write guards
+    fn write_all<'a, U: LabelledGeneric>(&'a self) -> Result<U, sync::Poisoned>
+    where
+        &'a Self: IntoLabelledGeneric,
+        <&'a Self as IntoLabelledGeneric>::Repr: sync::LockAllRw<'a, WriteGuards = U::Repr>;
+
+    /// Like [read_all](WithLabelledGeneric::read_all) for a struct of [tokio::sync::RwLock]s
+    #[cfg(feature = "tokio")]
+    fn read_all_async<'a, U: LabelledGeneric>(&'a self) -> impl std::future::Future<Output = U>
+    where
+        &'a Self: IntoLabelledGeneric,
+        <&'a Self as IntoLabelledGeneric>::Repr: sync::LockAllRwAsync<'a, ReadGuards = U::Repr>;
+
+    /// Like [write_all](WithLabelledGeneric::write_all) for a struct of [tokio::sync::RwLock]s
+    #[cfg(feature = "tokio")]
+    fn write_all_async<'a, U: LabelledGeneric>(&'a self) -> impl std::future::Future<Output = U>
+    where
+        &'a Self: IntoLabelledGeneric,
+        <&'a Self as IntoLabelledGeneric>::Repr: sync::LockAllRwAsync<'a, WriteGuards = U::Repr>;
+}
+
+impl<T: LabelledGeneric> WithLabelledGeneric for T {
+    fn hmap<U: LabelledGeneric, F>(self, f: F) -> U
+    where
+        Self::Repr: HMappable<Poly<F>, Output = U::Repr>,
     {
-        LabelledGeneric::from(LabelledGeneric::into(self).zip(LabelledGeneric::into(other)))
+        LabelledGeneric::from(LabelledGeneric::into(self).map(Poly(f)))
+    }
+
+    fn hmap_named<U: LabelledGeneric, F>(self, f: F) -> U
+    where
+        Self::Repr: labelled::HMapNamed<F, Output = U::Repr>,
+    {
+        LabelledGeneric::from(labelled::HMapNamed::hmap_named(
+            LabelledGeneric::into(self),
+            f,
+        ))
+    }
+
+    fn build_with_named<F>(f: F) -> Self
+    where
+        Self::Repr: labelled::BuildNamed<F>,
+    {
+        LabelledGeneric::from(labelled::BuildNamed::build_named(f))
+    }
+
+    #[cfg(feature = "csv")]
+    fn from_csv_record_named<F, E>(
+        headers: &::csv::StringRecord,
+        record: &::csv::StringRecord,
+        f: F,
+    ) -> Result<Self, csv::RecordError<E>>
+    where
+        Self::Repr: csv::FromRecordNamed<F, E>,
+    {
+        csv::FromRecordNamed::from_record_named(headers, record, f).map(LabelledGeneric::from)
+    }
+
+    fn from_env(prefix: &str) -> Result<Self, env::EnvError>
+    where
+        Self::Repr: env::FromEnv,
+    {
+        Self::from_env_with(prefix, |var| std::env::var(var))
+    }
+
+    fn from_env_with(
+        prefix: &str,
+        mut lookup: impl FnMut(&str) -> Result<String, std::env::VarError>,
+    ) -> Result<Self, env::EnvError>
+    where
+        Self::Repr: env::FromEnv,
+    {
+        let mut errors = Vec::new();
+        match env::FromEnv::from_env(prefix, &mut lookup, &mut errors) {
+            Some(repr) if errors.is_empty() => Ok(LabelledGeneric::from(repr)),
+            _ => Err(env::EnvError(errors)),
+        }
+    }
+
+    fn from_row<R: ?Sized, F, E>(row: &R, f: F) -> Result<Self, row::ColumnError<E>>
+    where
+        Self::Repr: row::FromRow<R, F, E>,
+    {
+        row::FromRow::from_row(row, f).map(LabelledGeneric::from)
+    }
+
+    fn parse_fields<'s, I: IntoIterator<Item = &'s str>>(
+        values: I,
+    ) -> Result<Self, parse::ParseError>
+    where
+        Self::Repr: parse::ParseFields<'s>,
+    {
+        let mut values = values.into_iter();
+        let repr = parse::ParseFields::parse_fields(&mut values, 0)?;
+        if values.next().is_some() {
+            return Err(parse::ParseError::TooManyValues {
+                expected: Self::Repr::LEN,
+            });
+        }
+        Ok(LabelledGeneric::from(repr))
+    }
+
+    fn build_default_with<F>(f: F) -> Self
+    where
+        Self::Repr: partial::Optionals + labelled::HMapNamed<F, Output = Self::Repr>,
+        <Self::Repr as partial::Optionals>::Options:
+            partial::FillMissing<partial::DefaultValue, Output = Self::Repr>,
+    {
+        partial::Partial::<Self>::new()
+            .fill_defaults()
+            .hmap_named(f)
+    }
+
+    fn hmap_sculpted<U: LabelledGeneric, F, Indices>(self, f: F) -> U
+    where
+        U::Repr: labelled::SculptMap<Self::Repr, F, Indices>,
+    {
+        LabelledGeneric::from(labelled::SculptMap::sculpt_map(
+            LabelledGeneric::into(self),
+            f,
+        ))
+    }
+
+    fn transmogrify_with<U, F, Indices>(self, mut f: F) -> U
+    where
+        Self: transmogrify::TransmogrifyWith<U, F, Indices>,
+    {
+        transmogrify::TransmogrifyWith::transmogrify_with(self, &mut f)
+    }
+
+    fn transpose_options<U: LabelledGeneric>(self) -> Option<U>
+    where
+        Self::Repr: TransposeOptions<Output = U::Repr>,
+    {
+        LabelledGeneric::into(self)
+            .transpose_options()
+            .map(LabelledGeneric::from)
+    }
+
+    fn fill_defaults<U: LabelledGeneric>(self) -> U
+    where
+        Self::Repr: partial::FillMissing<partial::DefaultValue, Output = U::Repr>,
+    {
+        LabelledGeneric::from(partial::FillMissing::fill_missing(
+            LabelledGeneric::into(self),
+            partial::DefaultValue,
+        ))
+    }
+
+    fn hzip<
+        U: LabelledGeneric,
+        TU: LabelledGeneric<Repr = <Self::Repr as HZippable<U::Repr>>::Zipped>,
+    >(
+        self,
+        other: U,
+    ) -> TU
+    where
+        Self::Repr: HZippable<U::Repr>,
+    {
+        LabelledGeneric::from(LabelledGeneric::into(self).zip(LabelledGeneric::into(other)))
+    }
+
+    fn hzip3<
+        U: LabelledGeneric,
+        V: LabelledGeneric,
+        TUV: LabelledGeneric<Repr = <Self::Repr as labelled::LabelledHZip3<U::Repr, V::Repr>>::Zipped>,
+    >(
+        self,
+        u: U,
+        v: V,
+    ) -> TUV
+    where
+        Self::Repr: labelled::LabelledHZip3<U::Repr, V::Repr>,
+    {
+        LabelledGeneric::from(labelled::LabelledHZip3::labelled_hzip3(
+            LabelledGeneric::into(self),
+            LabelledGeneric::into(u),
+            LabelledGeneric::into(v),
+        ))
+    }
+
+    fn map_to_list<F, U>(self, f: F) -> ConsList<U, <Self::Repr as MapToList<F, U>>::Output>
+    where
+        Self::Repr: MapToList<F, U>,
+    {
+        LabelledGeneric::into(self).map_to_list(f)
+    }
+
+    fn map_to_named_list<F, U>(
+        self,
+        f: F,
+    ) -> ConsList<
+        (&'static str, U),
+        <Self::Repr as MapToList<labelled::Named<F>, (&'static str, U)>>::Output,
+    >
+    where
+        Self::Repr: MapToList<labelled::Named<F>, (&'static str, U)>,
+    {
+        LabelledGeneric::into(self).map_to_list(labelled::Named(f))
+    }
+
+    fn for_each<F>(self, f: F)
+    where
+        Self::Repr: ForEach<F>,
+    {
+        LabelledGeneric::into(self).for_each(f)
+    }
+
+    fn for_each_named<F>(self, f: F)
+    where
+        Self::Repr: labelled::ForEachNamed<F>,
+    {
+        labelled::ForEachNamed::for_each_named(LabelledGeneric::into(self), f)
+    }
+
+    fn fields_into_iter<U>(self) -> impl Iterator<Item = U>
+    where
+        Self::Repr: MapToList<labelled::FieldValue, U>,
+    {
+        self.map_to_list(labelled::FieldValue).into_iter()
+    }
+
+    fn to_hashmap<U>(self) -> HashMap<&'static str, U>
+    where
+        Self::Repr: MapToList<labelled::Named<Identity>, (&'static str, U)>,
+    {
+        self.map_to_named_list(Identity).into_iter().collect()
+    }
+
+    fn to_btreemap<U>(self) -> BTreeMap<&'static str, U>
+    where
+        Self::Repr: MapToList<labelled::Named<Identity>, (&'static str, U)>,
+    {
+        self.map_to_named_list(Identity).into_iter().collect()
+    }
+
+    fn iter_named<'a, U: 'a>(&'a self) -> impl Iterator<Item = (&'static str, &'a U)>
+    where
+        &'a Self: IntoLabelledGeneric,
+        <&'a Self as IntoLabelledGeneric>::Repr:
+            MapToList<labelled::Named<Identity>, (&'static str, &'a U)>,
+    {
+        IntoLabelledGeneric::into(self)
+            .map_to_list(labelled::Named(Identity))
+            .into_iter()
+    }
+
+    fn diff<'a>(&'a self, other: &'a Self) -> Vec<&'static str>
+    where
+        &'a Self: IntoLabelledGeneric,
+        <&'a Self as IntoLabelledGeneric>::Repr: partial::Diff,
+    {
+        let mut changed = Vec::new();
+        partial::Diff::diff(
+            IntoLabelledGeneric::into(self),
+            IntoLabelledGeneric::into(other),
+            &mut changed,
+        );
+        changed
+    }
+
+    fn diff_patch<'a>(&'a self, other: Self) -> partial::Partial<Self>
+    where
+        Self::Repr: partial::Optionals,
+        &'a Self: IntoLabelledGeneric,
+        <&'a Self as IntoLabelledGeneric>::Repr:
+            partial::DiffPatch<Self::Repr, Options = <Self::Repr as partial::Optionals>::Options>,
+    {
+        partial::Partial {
+            options: partial::DiffPatch::diff_patch(
+                IntoLabelledGeneric::into(self),
+                LabelledGeneric::into(other),
+            ),
+        }
+    }
+
+    fn from_hashmap<U, K: Borrow<str> + Hash + Eq, S: BuildHasher>(
+        mut map: HashMap<K, U, S>,
+    ) -> Result<Self, labelled::MissingField>
+    where
+        Self: Sized,
+        Self::Repr: labelled::TakeNamed<U>,
+    {
+        labelled::TakeNamed::take_named(&mut |name| map.remove(name)).map(LabelledGeneric::from)
+    }
+
+    fn field_names() -> ConsList<&'static str, <Self::Repr as field_names::FieldNames>::Names>
+    where
+        Self::Repr: field_names::FieldNames,
+    {
+        field_names::field_names::<Self::Repr>()
+    }
+
+    fn validate<U: LabelledGeneric, F, E>(self, f: F) -> Result<U, validate::FieldErrors<E>>
+    where
+        Self::Repr: validate::Validate<F, E, Output = U::Repr>,
+    {
+        let mut errors = Vec::new();
+        match validate::Validate::validate(LabelledGeneric::into(self), f, &mut errors) {
+            Some(repr) => Ok(LabelledGeneric::from(repr)),
+            None => Err(errors),
+        }
+    }
+
+    #[cfg(feature = "futures")]
+    async fn hmap_async<U: LabelledGeneric, F>(self, f: F) -> U
+    where
+        Self::Repr: future::HMapNamedAsync<labelled::IgnoreName<F>, Output = U::Repr>,
+    {
+        self.hmap_named_async(labelled::IgnoreName(f)).await
+    }
+
+    #[cfg(feature = "futures")]
+    async fn hmap_async_sequential<U: LabelledGeneric, F>(self, f: F) -> U
+    where
+        Self::Repr: future::HMapNamedAsync<labelled::IgnoreName<F>, Output = U::Repr>,
+    {
+        LabelledGeneric::from(
+            future::HMapNamedAsync::hmap_named_sequential(
+                LabelledGeneric::into(self),
+                labelled::IgnoreName(f),
+            )
+            .await,
+        )
+    }
+
+    #[cfg(feature = "futures")]
+    async fn hmap_named_async<U: LabelledGeneric, F>(self, f: F) -> U
+    where
+        Self::Repr: future::HMapNamedAsync<F, Output = U::Repr>,
+    {
+        LabelledGeneric::from(
+            future::HMapNamedAsync::hmap_named_concurrent(LabelledGeneric::into(self), f).await,
+        )
+    }
+
+    #[cfg(feature = "futures")]
+    async fn for_each_async<F>(self, f: F)
+    where
+        Self::Repr: future::HMapNamedAsync<labelled::IgnoreName<F>>,
+    {
+        future::HMapNamedAsync::hmap_named_concurrent(
+            LabelledGeneric::into(self),
+            labelled::IgnoreName(f),
+        )
+        .await;
+    }
+
+    #[cfg(feature = "futures")]
+    async fn for_each_async_sequential<F>(self, f: F)
+    where
+        Self::Repr: future::HMapNamedAsync<labelled::IgnoreName<F>>,
+    {
+        future::HMapNamedAsync::hmap_named_sequential(
+            LabelledGeneric::into(self),
+            labelled::IgnoreName(f),
+        )
+        .await;
+    }
+
+    #[cfg(feature = "futures")]
+    async fn map_to_list_async<F, U>(
+        self,
+        f: F,
+    ) -> ConsList<
+        U,
+        <<Self::Repr as future::HMapNamedAsync<labelled::IgnoreName<F>>>::Output as MapToList<
+            labelled::FieldValue,
+            U,
+        >>::Output,
+    >
+    where
+        Self::Repr: future::HMapNamedAsync<labelled::IgnoreName<F>>,
+        <Self::Repr as future::HMapNamedAsync<labelled::IgnoreName<F>>>::Output:
+            MapToList<labelled::FieldValue, U>,
+    {
+        future::HMapNamedAsync::hmap_named_concurrent(
+            LabelledGeneric::into(self),
+            labelled::IgnoreName(f),
+        )
+        .await
+        .map_to_list(labelled::FieldValue)
+    }
+
+    #[cfg(feature = "futures")]
+    async fn validate_async<U: LabelledGeneric, F, E>(
+        self,
+        f: F,
+    ) -> Result<U, validate::FieldErrors<E>>
+    where
+        Self::Repr: future::ValidateAsync<F, E, Output = U::Repr>,
+    {
+        future::ValidateAsync::validate_async(LabelledGeneric::into(self), f)
+            .await
+            .map(LabelledGeneric::from)
+    }
+
+    #[cfg(feature = "tracing")]
+    async fn hmap_async_traced<U: LabelledGeneric, F>(self, f: F) -> U
+    where
+        Self::Repr: future::HMapNamedAsync<future::Traced<F>, Output = U::Repr>,
+    {
+        LabelledGeneric::from(
+            future::HMapNamedAsync::hmap_named_concurrent(
+                LabelledGeneric::into(self),
+                future::Traced(f),
+            )
+            .await,
+        )
+    }
+
+    #[cfg(feature = "tracing")]
+    async fn for_each_async_traced<F>(self, f: F)
+    where
+        Self::Repr: future::HMapNamedAsync<future::Traced<F>>,
+    {
+        future::HMapNamedAsync::hmap_named_concurrent(
+            LabelledGeneric::into(self),
+            future::Traced(f),
+        )
+        .await;
+    }
+
+    fn transpose_results<U: LabelledGeneric, E>(self) -> Result<U, (&'static str, E)>
+    where
+        Self::Repr: TryHMap<labelled::UnwrapNamedResult, (&'static str, E), Output = U::Repr>,
+    {
+        LabelledGeneric::into(self)
+            .try_hmap(labelled::UnwrapNamedResult)
+            .map(LabelledGeneric::from)
+    }
+
+    fn transpose_results_all<U: LabelledGeneric, E>(self) -> Result<U, validate::FieldErrors<E>>
+    where
+        Self::Repr: validate::Validate<UnwrapResult, E, Output = U::Repr>,
+    {
+        self.validate(UnwrapResult)
+    }
+
+    fn lock_all<'a, U: LabelledGeneric>(&'a self) -> Result<U, sync::Poisoned>
+    where
+        &'a Self: IntoLabelledGeneric,
+        <&'a Self as IntoLabelledGeneric>::Repr: sync::LockAll<'a, Guards = U::Repr>,
+    {
+        sync::LockAll::lock_all(IntoLabelledGeneric::into(self)).map(LabelledGeneric::from)
+    }
+
+    #[cfg(feature = "tokio")]
+    async fn lock_all_async<'a, U: LabelledGeneric>(&'a self) -> U
+    where
+        &'a Self: IntoLabelledGeneric,
+        <&'a Self as IntoLabelledGeneric>::Repr: sync::LockAllAsync<'a, Guards = U::Repr>,
+    {
+        LabelledGeneric::from(
+            sync::LockAllAsync::lock_all_async(IntoLabelledGeneric::into(self)).await,
+        )
+    }
+
+    fn read_all<'a, U: LabelledGeneric>(&'a self) -> Result<U, sync::Poisoned>
+    where
+        &'a Self: IntoLabelledGeneric,
+        <&'a Self as IntoLabelledGeneric>::Repr: sync::LockAllRw<'a, ReadGuards = U::Repr>,
+    {
+        sync::LockAllRw::read_all(IntoLabelledGeneric::into(self)).map(LabelledGeneric::from)
+    }
+
+    fn write_all<'a, U: LabelledGeneric>(&'a self) -> Result<U, sync::Poisoned>
+    where
+        &'a Self: IntoLabelledGeneric,
+        <&'a Self as IntoLabelledGeneric>::Repr: sync::LockAllRw<'a, WriteGuards = U::Repr>,
+    {
+        sync::LockAllRw::write_all(IntoLabelledGeneric::into(self)).map(LabelledGeneric::from)
+    }
+
+    #[cfg(feature = "tokio")]
+    async fn read_all_async<'a, U: LabelledGeneric>(&'a self) -> U
+    where
+        &'a Self: IntoLabelledGeneric,
+        <&'a Self as IntoLabelledGeneric>::Repr: sync::LockAllRwAsync<'a, ReadGuards = U::Repr>,
+    {
+        LabelledGeneric::from(
+            sync::LockAllRwAsync::read_all_async(IntoLabelledGeneric::into(self)).await,
+        )
+    }
+
+    #[cfg(feature = "tokio")]
+    async fn write_all_async<'a, U: LabelledGeneric>(&'a self) -> U
+    where
+        &'a Self: IntoLabelledGeneric,
+        <&'a Self as IntoLabelledGeneric>::Repr: sync::LockAllRwAsync<'a, WriteGuards = U::Repr>,
+    {
+        LabelledGeneric::from(
+            sync::LockAllRwAsync::write_all_async(IntoLabelledGeneric::into(self)).await,
+        )
+    }
+}
+
+/// Borrowing traversals for anything that can produce an HList of references to its fields through [ToRef]. This
+/// includes HLists themselves as well as any struct that implements [ToRef].
+pub trait WithGenericRef<'a> {
+    type Refs: HList;
+
+    fn for_each_ref<F>(&'a self, f: F)
+    where
+        Self::Refs: ForEach<F>;
+
+    fn map_ref_to_list<F, U>(
+        &'a self,
+        f: F,
+    ) -> ConsList<U, <Self::Refs as MapToList<F, U>>::Output>
+    where
+        Self::Refs: MapToList<F, U>;
+
+    /// Allows getting an iterator over references to the fields if they all have the same type
+    fn fields_iter<U: 'a>(&'a self) -> impl Iterator<Item = &'a U>
+    where
+        Self::Refs: MapToList<Identity, &'a U>;
+
+    /// Like [fields_of_type](WithGeneric::fields_of_type) but collects references. The same `'static` requirement
+    /// applies to the field types, though not to the borrow of `self`.
+    fn fields_of_type_ref<U: 'static>(&'a self) -> Vec<&'a U>
+    where
+        Self::Refs: HFold<RefsOfType<U>, Vec<&'a U>>;
+}
+
+impl<'a, T: ToRef<'a>> WithGenericRef<'a> for T
+where
+    T::Output: HList,
+{
+    type Refs = T::Output;
+
+    fn for_each_ref<F>(&'a self, f: F)
+    where
+        Self::Refs: ForEach<F>,
+    {
+        self.to_ref().for_each(f)
+    }
+
+    fn map_ref_to_list<F, U>(&'a self, f: F) -> ConsList<U, <Self::Refs as MapToList<F, U>>::Output>
+    where
+        Self::Refs: MapToList<F, U>,
+    {
+        self.to_ref().map_to_list(f)
+    }
+
+    fn fields_iter<U: 'a>(&'a self) -> impl Iterator<Item = &'a U>
+    where
+        Self::Refs: MapToList<Identity, &'a U>,
+    {
+        self.map_ref_to_list(Identity).into_iter()
+    }
+
+    fn fields_of_type_ref<U: 'static>(&'a self) -> Vec<&'a U>
+    where
+        Self::Refs: HFold<RefsOfType<U>, Vec<&'a U>>,
+    {
+        self.to_ref().hfold(Vec::new(), RefsOfType(PhantomData))
+    }
+}
+
+/// In-place traversals for anything that can produce an HList of mutable references to its fields through [ToMut]
+pub trait WithGenericMut<'a> {
+    type Muts: HList;
+
+    /// Visit each field by mutable reference so it can be modified in place
+    fn for_each_mut<F>(&'a mut self, f: F)
+    where
+        Self::Muts: ForEach<F>;
+
+    /// Allows getting an iterator over mutable references to the fields if they all have the same type
+    fn fields_iter_mut<U: 'a>(&'a mut self) -> impl Iterator<Item = &'a mut U>
+    where
+        Self::Muts: MapToList<Identity, &'a mut U>;
+}
+
+impl<'a, T: ToMut<'a>> WithGenericMut<'a> for T
+where
+    T::Output: HList,
+{
+    type Muts = T::Output;
+
+    fn for_each_mut<F>(&'a mut self, f: F)
+    where
+        Self::Muts: ForEach<F>,
+    {
+        self.to_mut().for_each(f)
+    }
+
+    fn fields_iter_mut<U: 'a>(&'a mut self) -> impl Iterator<Item = &'a mut U>
+    where
+        Self::Muts: MapToList<Identity, &'a mut U>,
+    {
+        self.to_mut().map_to_list(Identity).into_iter()
+    }
+}
+
+pub trait MapToList<F, U>: HList {
+    type Output: ConsListT<U>;
+
+    /// The length of the list produced by [map_to_list](MapToList::map_to_list)
+    const LEN: usize = <Self::Output as ConsListT<U>>::LEN;
+
+    /// Map a monomorphizing function over the HList to produce an [iterable](`ConsList::into_iter`) datastructure which
+    /// lives fully on stack
+    fn map_to_list(self, f: F) -> ConsList<U, Self::Output>;
+
+    /// Like [map_to_list](MapToList::map_to_list) but produces a plain array. Fails to compile if `N` is not the
+    /// length of the HList.
+    fn map_to_array<const N: usize>(self, f: F) -> [U; N] {
+        self.map_to_list(f).into_array()
+    }
+}
+
+impl<F, U> MapToList<F, U> for HNil {
+    type Output = cons_list::Nil<U>;
+
+    fn map_to_list(self, _f: F) -> ConsList<U, Self::Output> {
+        ConsList::nil()
+    }
+}
+
+impl<F: Func<Head, Output = U>, U, Head, Tail: MapToList<F, U>> MapToList<F, U>
+    for HCons<Head, Tail>
+{
+    type Output = cons_list::Cons<U, <Tail as MapToList<F, U>>::Output>;
+
+    fn map_to_list(self, mut f: F) -> ConsList<U, Self::Output> {
+        let HCons { head, tail } = self;
+        ConsList::cons(f.call(head), tail.map_to_list(f))
+    }
+}
+
+/// The inverse of [MapToList] with [Identity]: rebuild an HList whose elements all have type `U` from a list
+pub trait FromList<U>: HList {
+    type List: ConsListT<U>;
+
+    fn from_list(list: ConsList<U, Self::List>) -> Self;
+}
+
+impl<U> FromList<U> for HNil {
+    type List = cons_list::Nil<U>;
+
+    fn from_list(_list: ConsList<U, Self::List>) -> Self {
+        HNil
+    }
+}
+
+impl<U, Tail: FromList<U>> FromList<U> for HCons<U, Tail> {
+    type List = cons_list::Cons<U, Tail::List>;
+
+    fn from_list(list: ConsList<U, Self::List>) -> Self {
+        let (head, tail) = list.pop();
+        HCons {
+            head,
+            tail: Tail::from_list(tail),
+        }
+    }
+}
+
+/// Returned when building a struct from an iterator that doesn't yield one item per field
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FieldCountMismatch {
+    TooFew { expected: usize, found: usize },
+    TooMany { expected: usize },
+}
+
+impl fmt::Display for FieldCountMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FieldCountMismatch::TooFew { expected, found } => {
+                write!(f, "expected {expected} fields but found only {found}")
+            }
+            FieldCountMismatch::TooMany { expected } => {
+                write!(f, "expected {expected} fields but found more")
+            }
+        }
+    }
+}
+
+impl Error for FieldCountMismatch {}
+
+/// Build an HList whose elements all have type `U` from the items of an iterator
+pub trait TakeFromIter<U>: HList {
+    /// Take one item per element. If the iterator runs out first, fails with the number of items it yielded.
+    fn take_from_iter(iter: &mut impl Iterator<Item = U>) -> Result<Self, usize>;
+}
+
+impl<U> TakeFromIter<U> for HNil {
+    fn take_from_iter(_iter: &mut impl Iterator<Item = U>) -> Result<Self, usize> {
+        Ok(HNil)
+    }
+}
+
+impl<U, Tail: TakeFromIter<U>> TakeFromIter<U> for HCons<U, Tail> {
+    fn take_from_iter(iter: &mut impl Iterator<Item = U>) -> Result<Self, usize> {
+        let head = iter.next().ok_or(0usize)?;
+        let tail = Tail::take_from_iter(iter).map_err(|found| found + 1)?;
+        Ok(HCons { head, tail })
+    }
+}
+
+/// Build an HList by calling `F` once per element with a [PhantomData] token for the element's type
+pub trait Build<F>: HList {
+    fn build(f: F) -> Self;
+}
+
+impl<F> Build<F> for HNil {
+    fn build(_f: F) -> Self {
+        HNil
+    }
+}
+
+impl<F: Func<PhantomData<T>, Output = T>, T, Tail: Build<F>> Build<F> for HCons<T, Tail> {
+    fn build(mut f: F) -> Self {
+        HCons {
+            head: f.call(PhantomData),
+            tail: Tail::build(f),
+        }
+    }
+}
+
+pub trait TryMapToList<F, U, E>: HList {
+    type Output: ConsListT<U>;
+
+    /// Fallible version of [MapToList::map_to_list]. If a field fails, the values produced for the preceding fields
+    /// are dropped before the error is returned.
+    fn try_map_to_list(self, f: F) -> Result<ConsList<U, Self::Output>, E>;
+}
+
+impl<F, U, E> TryMapToList<F, U, E> for HNil {
+    type Output = cons_list::Nil<U>;
+
+    fn try_map_to_list(self, _f: F) -> Result<ConsList<U, Self::Output>, E> {
+        Ok(ConsList::nil())
+    }
+}
+
+impl<F: TryFunc<Head, Output = U, Error = E>, U, E, Head, Tail: TryMapToList<F, U, E>>
+    TryMapToList<F, U, E> for HCons<Head, Tail>
+{
+    type Output = cons_list::Cons<U, <Tail as TryMapToList<F, U, E>>::Output>;
+
+    fn try_map_to_list(self, mut f: F) -> Result<ConsList<U, Self::Output>, E> {
+        let HCons { head, tail } = self;
+        // `head` is an ordinary local until the tail succeeds, so it is dropped normally on the error path.
+        let head = f.try_call(head)?;
+        Ok(ConsList::cons(head, tail.try_map_to_list(f)?))
+    }
+}
+
+pub trait ForEach<F>: HList {
+    fn for_each(self, f: F);
+}
+
+impl<F> ForEach<F> for HNil {
+    fn for_each(self, _: F) {}
+}
+
+impl<F: Func<Head, Output = ()>, Head, Tail: ForEach<F>> ForEach<F> for HCons<Head, Tail> {
+    fn for_each(self, mut f: F) {
+        let HCons { head, tail } = self;
+        f.call(head);
+        tail.for_each(f)
+    }
+}
+
+pub trait ForEachRev<F>: HList {
+    fn for_each_rev(self, f: &mut F);
+}
+
+impl<F> ForEachRev<F> for HNil {
+    fn for_each_rev(self, _: &mut F) {}
+}
+
+impl<F: Func<Head, Output = ()>, Head, Tail: ForEachRev<F>> ForEachRev<F> for HCons<Head, Tail> {
+    fn for_each_rev(self, f: &mut F) {
+        let HCons { head, tail } = self;
+        tail.for_each_rev(f);
+        f.call(head)
+    }
+}
+
+pub trait All<F>: HList {
+    fn all(self, f: F) -> bool;
+}
+
+impl<F> All<F> for HNil {
+    fn all(self, _: F) -> bool {
+        true
+    }
+}
+
+impl<F: Func<Head, Output = bool>, Head, Tail: All<F>> All<F> for HCons<Head, Tail> {
+    fn all(self, mut f: F) -> bool {
+        let HCons { head, tail } = self;
+        f.call(head) && tail.all(f)
+    }
+}
+
+pub trait Any<F>: HList {
+    fn any(self, f: F) -> bool;
+}
+
+impl<F> Any<F> for HNil {
+    fn any(self, _: F) -> bool {
+        false
+    }
+}
+
+impl<F: Func<Head, Output = bool>, Head, Tail: Any<F>> Any<F> for HCons<Head, Tail> {
+    fn any(self, mut f: F) -> bool {
+        let HCons { head, tail } = self;
+        f.call(head) || tail.any(f)
+    }
+}
+
+pub trait FindMap<F, U>: HList {
+    fn find_map(self, f: F) -> Option<U>;
+}
+
+impl<F, U> FindMap<F, U> for HNil {
+    fn find_map(self, _: F) -> Option<U> {
+        None
+    }
+}
+
+impl<F: Func<Head, Output = Option<U>>, U, Head, Tail: FindMap<F, U>> FindMap<F, U>
+    for HCons<Head, Tail>
+{
+    fn find_map(self, mut f: F) -> Option<U> {
+        let HCons { head, tail } = self;
+        f.call(head).or_else(|| tail.find_map(f))
+    }
+}
+
+pub trait Position<F>: HList {
+    fn position(self, f: F) -> Option<usize>;
+}
+
+impl<F> Position<F> for HNil {
+    fn position(self, _: F) -> Option<usize> {
+        None
+    }
+}
+
+impl<F: Func<Head, Output = bool>, Head, Tail: Position<F>> Position<F> for HCons<Head, Tail> {
+    fn position(self, mut f: F) -> Option<usize> {
+        let HCons { head, tail } = self;
+        if f.call(head) {
+            Some(0)
+        } else {
+            tail.position(f).map(|i| i + 1)
+        }
+    }
+}
+
+pub trait MapReduce<F, U>: HList {
+    fn map_reduce<R: FnMut(U, U) -> U>(self, f: F, reducer: R) -> Option<U>;
+
+    /// Like [map_reduce](MapReduce::map_reduce) but starting from an already-reduced value `acc`
+    fn map_reduce_onto<R: FnMut(U, U) -> U>(self, acc: U, f: F, reducer: R) -> U;
+}
+
+impl<F, U> MapReduce<F, U> for HNil {
+    fn map_reduce<R: FnMut(U, U) -> U>(self, _: F, _: R) -> Option<U> {
+        None
+    }
+
+    fn map_reduce_onto<R: FnMut(U, U) -> U>(self, acc: U, _: F, _: R) -> U {
+        acc
+    }
+}
+
+impl<F: Func<Head, Output = U>, U, Head, Tail: MapReduce<F, U>> MapReduce<F, U>
+    for HCons<Head, Tail>
+{
+    fn map_reduce<R: FnMut(U, U) -> U>(self, mut f: F, reducer: R) -> Option<U> {
+        let HCons { head, tail } = self;
+        let acc = f.call(head);
+        Some(tail.map_reduce_onto(acc, f, reducer))
+    }
+
+    fn map_reduce_onto<R: FnMut(U, U) -> U>(self, acc: U, mut f: F, mut reducer: R) -> U {
+        let HCons { head, tail } = self;
+        let acc = reducer(acc, f.call(head));
+        tail.map_reduce_onto(acc, f, reducer)
+    }
+}
+
+pub trait ReplaceField<T, Index>: HList {
+    fn replace_field<F: Func<T, Output = T>>(self, f: F) -> Self;
+}
+
+impl<T, Tail: HList> ReplaceField<T, Here> for HCons<T, Tail> {
+    fn replace_field<F: Func<T, Output = T>>(self, mut f: F) -> Self {
+        let HCons { head, tail } = self;
+        HCons {
+            head: f.call(head),
+            tail,
+        }
+    }
+}
+
+impl<T, Head, Tail: ReplaceField<T, TailIndex>, TailIndex> ReplaceField<T, There<TailIndex>>
+    for HCons<Head, Tail>
+{
+    fn replace_field<F: Func<T, Output = T>>(self, f: F) -> Self {
+        let HCons { head, tail } = self;
+        HCons {
+            head,
+            tail: tail.replace_field(f),
+        }
+    }
+}
+
+pub trait TransposeOptions: HList {
+    type Output: HList;
+
+    fn transpose_options(self) -> Option<Self::Output>;
+}
+
+impl TransposeOptions for HNil {
+    type Output = HNil;
+
+    fn transpose_options(self) -> Option<Self::Output> {
+        Some(HNil)
+    }
+}
+
+impl<Head, Tail: TransposeOptions> TransposeOptions for HCons<Option<Head>, Tail> {
+    type Output = HCons<Head, Tail::Output>;
+
+    fn transpose_options(self) -> Option<Self::Output> {
+        let HCons { head, tail } = self;
+        Some(HCons {
+            head: head?,
+            tail: tail.transpose_options()?,
+        })
+    }
+}
+
+pub trait TransposeResultsAll<E>: HList {
+    type Output: HList;
+
+    /// Unwrap every `Ok` value. The error of every failed field is pushed onto `errors` along with its position
+    /// counting from `index`, and the unwrapped HList is only produced if every field succeeded.
+    fn transpose_results_all(
+        self,
+        index: usize,
+        errors: &mut Vec<(usize, E)>,
+    ) -> Option<Self::Output>;
+}
+
+impl<E> TransposeResultsAll<E> for HNil {
+    type Output = HNil;
+
+    fn transpose_results_all(
+        self,
+        _index: usize,
+        _errors: &mut Vec<(usize, E)>,
+    ) -> Option<Self::Output> {
+        Some(HNil)
+    }
+}
+
+impl<Head, E, Tail: TransposeResultsAll<E>> TransposeResultsAll<E>
+    for HCons<Result<Head, E>, Tail>
+{
+    type Output = HCons<Head, Tail::Output>;
+
+    fn transpose_results_all(
+        self,
+        index: usize,
+        errors: &mut Vec<(usize, E)>,
+    ) -> Option<Self::Output> {
+        let HCons { head, tail } = self;
+        let head = head.map_err(|err| errors.push((index, err))).ok();
+        let tail = tail.transpose_results_all(index + 1, errors);
+        Some(HCons {
+            head: head?,
+            tail: tail?,
+        })
+    }
+}
+
+pub trait ZipIterators: HList {
+    type Item: HList;
+
+    /// Advance every iterator, returning `None` if any of them is exhausted
+    fn next_fields(&mut self) -> Option<Self::Item>;
+}
+
+impl ZipIterators for HNil {
+    type Item = HNil;
+
+    fn next_fields(&mut self) -> Option<Self::Item> {
+        Some(HNil)
+    }
+}
+
+impl<Head: Iterator, Tail: ZipIterators> ZipIterators for HCons<Head, Tail> {
+    type Item = HCons<Head::Item, Tail::Item>;
+
+    fn next_fields(&mut self) -> Option<Self::Item> {
+        Some(HCons {
+            head: self.head.next()?,
+            tail: self.tail.next_fields()?,
+        })
+    }
+}
+
+pub trait HZip3<B, C>: HList {
+    type Zipped: HList;
+
+    /// Zip three equal-length HLists into an HList of triples
+    fn hzip3(self, b: B, c: C) -> Self::Zipped;
+}
+
+impl HZip3<HNil, HNil> for HNil {
+    type Zipped = HNil;
+
+    fn hzip3(self, _b: HNil, _c: HNil) -> Self::Zipped {
+        HNil
+    }
+}
+
+impl<A, B, C, ATail: HZip3<BTail, CTail>, BTail, CTail> HZip3<HCons<B, BTail>, HCons<C, CTail>>
+    for HCons<A, ATail>
+{
+    type Zipped = HCons<(A, B, C), ATail::Zipped>;
+
+    fn hzip3(self, b: HCons<B, BTail>, c: HCons<C, CTail>) -> Self::Zipped {
+        HCons {
+            head: (self.head, b.head, c.head),
+            tail: self.tail.hzip3(b.tail, c.tail),
+        }
+    }
+}
+
+pub trait HUnzip: HList {
+    type Left: HList;
+    type Right: HList;
+
+    /// Split an HList of pairs into an HList of the first elements and an HList of the second elements
+    fn hunzip(self) -> (Self::Left, Self::Right);
+}
+
+impl HUnzip for HNil {
+    type Left = HNil;
+    type Right = HNil;
+
+    fn hunzip(self) -> (Self::Left, Self::Right) {
+        (HNil, HNil)
+    }
+}
+
+impl<A, B, Tail: HUnzip> HUnzip for HCons<(A, B), Tail> {
+    type Left = HCons<A, Tail::Left>;
+    type Right = HCons<B, Tail::Right>;
+
+    fn hunzip(self) -> (Self::Left, Self::Right) {
+        let HCons { head: (a, b), tail } = self;
+        let (a_tail, b_tail) = tail.hunzip();
+        (
+            HCons {
+                head: a,
+                tail: a_tail,
+            },
+            HCons {
+                head: b,
+                tail: b_tail,
+            },
+        )
+    }
+}
+
+pub trait HMap2<Other, F>: HList {
+    type Output;
+
+    /// Map a binary function over the corresponding elements of two equal-length HLists
+    fn hmap2(self, other: Other, f: F) -> Self::Output;
+}
+
+impl<F> HMap2<HNil, F> for HNil {
+    type Output = HNil;
+
+    fn hmap2(self, _other: HNil, _f: F) -> Self::Output {
+        HNil
+    }
+}
+
+impl<F: Func2<A, B>, A, B, ATail: HMap2<BTail, F>, BTail> HMap2<HCons<B, BTail>, F>
+    for HCons<A, ATail>
+{
+    type Output = HCons<F::Output, <ATail as HMap2<BTail, F>>::Output>;
+
+    fn hmap2(self, other: HCons<B, BTail>, mut f: F) -> Self::Output {
+        let HCons {
+            head: a,
+            tail: a_tail,
+        } = self;
+        let HCons {
+            head: b,
+            tail: b_tail,
+        } = other;
+        HCons {
+            head: f.call(a, b),
+            tail: a_tail.hmap2(b_tail, f),
+        }
+    }
+}
+
+pub trait HMap3<B, C, F>: HList {
+    type Output;
+
+    /// Map a ternary function over the corresponding elements of three equal-length HLists
+    fn hmap3(self, b: B, c: C, f: F) -> Self::Output;
+}
+
+impl<F> HMap3<HNil, HNil, F> for HNil {
+    type Output = HNil;
+
+    fn hmap3(self, _b: HNil, _c: HNil, _f: F) -> Self::Output {
+        HNil
+    }
+}
+
+impl<F: Func3<A, B, C>, A, B, C, ATail: HMap3<BTail, CTail, F>, BTail, CTail>
+    HMap3<HCons<B, BTail>, HCons<C, CTail>, F> for HCons<A, ATail>
+{
+    type Output = HCons<F::Output, <ATail as HMap3<BTail, CTail, F>>::Output>;
+
+    fn hmap3(self, b: HCons<B, BTail>, c: HCons<C, CTail>, mut f: F) -> Self::Output {
+        HCons {
+            head: f.call(self.head, b.head, c.head),
+            tail: self.tail.hmap3(b.tail, c.tail, f),
+        }
     }
+}
 
-    fn map_to_list<F, U>(self, f: F) -> ConsList<U, <Self::Repr as MapToList<F, U>>::Output>
-    where
-        Self::Repr: MapToList<F, U>,
-    {
-        LabelledGeneric::into(self).map_to_list(f)
+pub trait HFold<F, Acc>: HList {
+    fn hfold(self, init: Acc, f: F) -> Acc;
+}
+
+impl<F, Acc> HFold<F, Acc> for HNil {
+    fn hfold(self, init: Acc, _: F) -> Acc {
+        init
     }
+}
 
-    fn for_each<F>(self, f: F)
-    where
-        Self::Repr: ForEach<F>,
-    {
-        LabelledGeneric::into(self).for_each(f)
+impl<F: Folder<Acc, Head>, Acc, Head, Tail: HFold<F, Acc>> HFold<F, Acc> for HCons<Head, Tail> {
+    fn hfold(self, init: Acc, mut f: F) -> Acc {
+        let HCons { head, tail } = self;
+        let acc = f.fold(init, head);
+        tail.hfold(acc, f)
     }
 }
 
-pub trait MapToList<F, U>: HList {
-    type Output: ConsListT<U>;
+pub trait MapAccum<F, Acc>: HList {
+    type Output: HList;
 
-    /// Map a monomorphizing function over the HList to produce an [iterable](`ConsList::into_iter`) datastructure which
-    /// lives fully on stack
-    fn map_to_list(self, f: F) -> ConsList<U, Self::Output>;
+    fn map_accum(self, init: Acc, f: F) -> (Self::Output, Acc);
 }
 
-impl<F, U> MapToList<F, U> for HNil {
-    type Output = cons_list::Nil<U>;
+impl<F, Acc> MapAccum<F, Acc> for HNil {
+    type Output = HNil;
 
-    fn map_to_list(self, _f: F) -> ConsList<U, Self::Output> {
-        ConsList::nil()
+    fn map_accum(self, init: Acc, _: F) -> (Self::Output, Acc) {
+        (HNil, init)
     }
 }
 
-impl<F: Func<Head, Output = U>, U, Head, Tail: MapToList<F, U>> MapToList<F, U>
+impl<F: Scanner<Acc, Head>, Acc, Head, Tail: MapAccum<F, Acc>> MapAccum<F, Acc>
     for HCons<Head, Tail>
 {
-    type Output = cons_list::Cons<U, <Tail as MapToList<F, U>>::Output>;
+    type Output = HCons<F::Output, Tail::Output>;
 
-    fn map_to_list(self, mut f: F) -> ConsList<U, Self::Output> {
+    fn map_accum(self, init: Acc, mut f: F) -> (Self::Output, Acc) {
         let HCons { head, tail } = self;
-        ConsList::cons(f.call(head), tail.map_to_list(f))
+        let (acc, head) = f.scan(init, head);
+        let (tail, acc) = tail.map_accum(acc, f);
+        (HCons { head, tail }, acc)
     }
 }
 
-pub trait ForEach<F>: HList {
-    fn for_each(self, f: F);
+pub trait TryForEach<F, E>: HList {
+    fn try_for_each(self, f: F) -> Result<(), E>;
 }
 
-impl<F> ForEach<F> for HNil {
-    fn for_each(self, _: F) {}
+impl<F, E> TryForEach<F, E> for HNil {
+    fn try_for_each(self, _: F) -> Result<(), E> {
+        Ok(())
+    }
 }
 
-impl<F: Func<Head, Output = ()>, Head, Tail: ForEach<F>> ForEach<F> for HCons<Head, Tail> {
-    fn for_each(self, mut f: F) {
+impl<F: TryFunc<Head, Output = (), Error = E>, E, Head, Tail: TryForEach<F, E>> TryForEach<F, E>
+    for HCons<Head, Tail>
+{
+    fn try_for_each(self, mut f: F) -> Result<(), E> {
         let HCons { head, tail } = self;
-        f.call(head);
-        tail.for_each(f)
+        f.try_call(head)?;
+        tail.try_for_each(f)
+    }
+}
+
+pub trait TryHMap<F, E>: HList {
+    type Output;
+
+    /// Map a fallible function over the HList, stopping at the first error
+    fn try_hmap(self, f: F) -> Result<Self::Output, E>;
+}
+
+impl<F, E> TryHMap<F, E> for HNil {
+    type Output = HNil;
+
+    fn try_hmap(self, _f: F) -> Result<Self::Output, E> {
+        Ok(HNil)
+    }
+}
+
+impl<F: TryFunc<Head, Error = E>, E, Head, Tail: TryHMap<F, E>> TryHMap<F, E>
+    for HCons<Head, Tail>
+{
+    type Output = HCons<F::Output, <Tail as TryHMap<F, E>>::Output>;
+
+    fn try_hmap(self, mut f: F) -> Result<Self::Output, E> {
+        let HCons { head, tail } = self;
+        let head = f.try_call(head)?;
+        Ok(HCons {
+            head,
+            tail: tail.try_hmap(f)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::TryFromIntError;
+
+    use super::*;
+
+    #[derive(Generic)]
+    struct Wide {
+        a: i64,
+        b: i32,
+    }
+
+    #[derive(Generic, Debug, PartialEq)]
+    struct Narrow {
+        a: u8,
+        b: u8,
+    }
+
+    struct ToU8;
+
+    impl<T: TryInto<u8, Error = TryFromIntError>> TryFunc<T> for ToU8 {
+        type Output = u8;
+        type Error = TryFromIntError;
+
+        fn try_call(&mut self, i: T) -> Result<u8, TryFromIntError> {
+            i.try_into()
+        }
+    }
+
+    #[test]
+    fn stock_funcs() {
+        let zeroed: Narrow = Wide { a: 1, b: 2 }.hmap(ConstFunc(0u8));
+        assert_eq!(zeroed, Narrow { a: 0, b: 0 });
+        Wide { a: 1, b: 2 }.for_each(DropFunc);
+        let wide = Wide { a: -1, b: 2 };
+        let refs = frunk::hlist![&wide.a, &wide.b];
+        let cloned: Wide = Generic::from(refs.map(Poly(CloneFunc)));
+        assert_eq!((cloned.a, cloned.b), (-1, 2));
+        let shown = wide.map_to_list(DebugFunc);
+        assert_eq!(shown.into_array(), ["-1", "2"]);
+    }
+
+    #[test]
+    fn frunk_func_adapter() {
+        struct Double;
+
+        impl frunk::Func<u8> for Double {
+            type Output = u8;
+
+            fn call(i: u8) -> u8 {
+                i * 2
+            }
+        }
+
+        let narrow: Narrow = Narrow { a: 1, b: 2 }.hmap(FrunkFuncAdapter::<Double>::new());
+        assert_eq!(narrow, Narrow { a: 2, b: 4 });
+        let list = Narrow { a: 3, b: 4 }.map_to_list(FrunkFuncAdapter::<Double>::new());
+        assert_eq!(list.into_array(), [6, 8]);
+    }
+
+    #[test]
+    fn fn_poly() {
+        let narrow: Narrow = Narrow { a: 1, b: 2 }.hmap(FnPoly(|x: u8| x * 10));
+        assert_eq!(narrow, Narrow { a: 10, b: 20 });
+        let mut sum = 0;
+        Narrow { a: 3, b: 4 }.for_each(FnPoly(|x: u8| sum += x));
+        assert_eq!(sum, 7);
+    }
+
+    #[test]
+    fn ctx_poly() {
+        let mut total = 0;
+        Wide { a: 3, b: 4 }.for_each(ctx_poly! {
+            <'a> { total: &'a mut i64 = &mut total, scale: i64 = 2 }
+            |a: i64| -> () { **total += a * *scale }
+            |b: i32| -> () { **total += i64::from(b) * *scale }
+        });
+        assert_eq!(total, 14);
+
+        let narrow: Narrow = Wide { a: 300, b: -5 }.hmap(ctx_poly! {
+            {}
+            |a: i64| -> u8 { a.clamp(0, 255) as u8 }
+            |b: i32| -> u8 { b.clamp(0, 255) as u8 }
+        });
+        assert_eq!(narrow, Narrow { a: 255, b: 0 });
+    }
+
+    #[cfg(feature = "derive")]
+    #[test]
+    fn fieldwise() {
+        #[derive(Generic, Fieldwise)]
+        struct Point {
+            x: i32,
+            y: i32,
+        }
+
+        #[derive(Generic, Fieldwise)]
+        struct Pair<T>(T, T);
+
+        struct Widen;
+        impl<T: Into<i64>> Func<T> for Widen {
+            type Output = i64;
+
+            fn call(&mut self, i: T) -> i64 {
+                i.into()
+            }
+        }
+
+        let p = Point { x: 1, y: 2 };
+        assert_eq!(p.fields_iter().copied().collect::<Vec<i32>>(), [1, 2]);
+        let pair: Pair<i64> = p.map_fields(Widen);
+        assert_eq!(pair.fields_iter().sum::<i64>(), 3);
+        let mut seen = Vec::new();
+        struct Push<'a>(&'a mut Vec<i64>);
+        impl Func<i64> for Push<'_> {
+            type Output = ();
+
+            fn call(&mut self, i: i64) {
+                self.0.push(i)
+            }
+        }
+        pair.for_each_field(Push(&mut seen));
+        assert_eq!(seen, [1, 2]);
+    }
+
+    #[test]
+    fn try_hmap() {
+        let narrow: Result<Narrow, _> = Wide { a: 1, b: 2 }.try_hmap(ToU8);
+        assert_eq!(narrow, Ok(Narrow { a: 1, b: 2 }));
+        let narrow: Result<Narrow, _> = Wide { a: 1, b: 256 }.try_hmap(ToU8);
+        assert!(narrow.is_err());
+    }
+
+    #[test]
+    fn try_for_each_stops_early() {
+        struct Check<'a>(&'a mut Vec<i64>);
+
+        impl<T: Into<i64>> TryFunc<T> for Check<'_> {
+            type Output = ();
+            type Error = i64;
+
+            fn try_call(&mut self, i: T) -> Result<(), i64> {
+                let i = i.into();
+                if i < 0 {
+                    return Err(i);
+                }
+                self.0.push(i);
+                Ok(())
+            }
+        }
+
+        let mut seen = Vec::new();
+        let result = Wide { a: -1, b: 2 }.try_for_each(Check(&mut seen));
+        assert_eq!(result, Err(-1));
+        assert!(seen.is_empty());
+        let result = Wide { a: 1, b: 2 }.try_for_each(Check(&mut seen));
+        assert_eq!(result, Ok(()));
+        assert_eq!(seen, vec![1, 2]);
+    }
+
+    #[test]
+    fn hmap2_combines_fields() {
+        struct Sum;
+
+        impl<A: Into<i64>, B: Into<i64>> Func2<A, B> for Sum {
+            type Output = i64;
+
+            fn call(&mut self, a: A, b: B) -> i64 {
+                a.into() + b.into()
+            }
+        }
+
+        #[derive(Generic, Debug, PartialEq)]
+        struct Sums {
+            a: i64,
+            b: i64,
+        }
+
+        let sums: Sums = Wide { a: 1, b: 2 }.hmap2(Narrow { a: 10, b: 20 }, Sum);
+        assert_eq!(sums, Sums { a: 11, b: 22 });
+    }
+
+    #[test]
+    fn from_fields_iter() {
+        #[derive(Generic, Debug, PartialEq)]
+        struct Rgb {
+            r: u8,
+            g: u8,
+            b: u8,
+        }
+
+        let rgb = Rgb { r: 1, g: 2, b: 3 };
+        let items: Vec<u8> = Rgb { r: 1, g: 2, b: 3 }.fields_into_iter().collect();
+        assert_eq!(Rgb::from_fields_iter(items), Ok(rgb));
+        assert_eq!(
+            Rgb::from_fields_iter([1, 2]),
+            Err(FieldCountMismatch::TooFew {
+                expected: 3,
+                found: 2
+            })
+        );
+        assert_eq!(
+            Rgb::from_fields_iter([1, 2, 3, 4]),
+            Err(FieldCountMismatch::TooMany { expected: 3 })
+        );
+
+        let mut pixels = [1, 2, 3, 4, 5, 6, 7].into_iter();
+        assert_eq!(
+            Rgb::from_fields_iter_prefix(&mut pixels),
+            Ok(Rgb { r: 1, g: 2, b: 3 })
+        );
+        assert_eq!(
+            Rgb::from_fields_iter_prefix(&mut pixels),
+            Ok(Rgb { r: 4, g: 5, b: 6 })
+        );
+        assert_eq!(pixels.next(), Some(7));
+    }
+
+    #[test]
+    fn from_fields_fn() {
+        #[derive(Generic, Debug, PartialEq)]
+        struct Axes {
+            x: String,
+            y: String,
+            z: String,
+        }
+
+        let axes = Axes::from_fields_fn(|i| format!("axis{i}"));
+        assert_eq!(axes.x, "axis0");
+        assert_eq!(axes.z, "axis2");
+        let squares: (u32, u32, u32, u32) = WithGeneric::from_fields_fn(|i| (i * i) as u32);
+        assert_eq!(squares, (0, 1, 4, 9));
+    }
+
+    #[test]
+    fn build_with_registry() {
+        use std::any::{Any, TypeId};
+
+        struct Registry(HashMap<TypeId, Box<dyn Any>>);
+
+        impl<T: Clone + 'static> Func<PhantomData<T>> for &Registry {
+            type Output = T;
+
+            fn call(&mut self, _i: PhantomData<T>) -> T {
+                self.0[&TypeId::of::<T>()]
+                    .downcast_ref::<T>()
+                    .unwrap()
+                    .clone()
+            }
+        }
+
+        let mut components: HashMap<TypeId, Box<dyn Any>> = HashMap::new();
+        components.insert(TypeId::of::<i64>(), Box::new(7i64));
+        components.insert(TypeId::of::<i32>(), Box::new(-2i32));
+        let registry = Registry(components);
+        let wide = Wide::build_with(&registry);
+        assert_eq!((wide.a, wide.b), (7, -2));
+    }
+
+    #[test]
+    fn merge_with() {
+        #[derive(Generic, Debug, PartialEq)]
+        struct Limits {
+            low: i64,
+            high: i64,
+        }
+
+        struct Widen;
+
+        impl Func2<i64, i64> for Widen {
+            type Output = i64;
+
+            fn call(&mut self, a: i64, b: i64) -> i64 {
+                if a.abs() >= b.abs() {
+                    a
+                } else {
+                    b
+                }
+            }
+        }
+
+        let merged = Limits { low: -3, high: 5 }.merge_with(Limits { low: -7, high: 2 }, Widen);
+        assert_eq!(merged, Limits { low: -7, high: 5 });
+
+        #[derive(Generic, Debug, PartialEq)]
+        struct Overrides {
+            name: Option<&'static str>,
+            level: Option<u8>,
+        }
+
+        let base = Overrides {
+            name: Some("base"),
+            level: Some(1),
+        };
+        let user = Overrides {
+            name: None,
+            level: Some(3),
+        };
+        assert_eq!(
+            base.merge_with(user, Coalesce),
+            Overrides {
+                name: Some("base"),
+                level: Some(3),
+            }
+        );
+    }
+
+    #[test]
+    fn hzip3_and_hmap3() {
+        #[derive(Generic)]
+        struct Triples {
+            a: (i64, u8, i64),
+            b: (i32, u8, i32),
+        }
+
+        let triples: Triples =
+            Wide { a: 1, b: 2 }.hzip3(Narrow { a: 3, b: 4 }, Wide { a: 5, b: 6 });
+        assert_eq!((triples.a, triples.b), ((1, 3, 5), (2, 4, 6)));
+
+        struct Delta;
+
+        impl<A: Into<i64>, B: Into<i64>, C: Into<i64>> Func3<A, B, C> for Delta {
+            type Output = u8;
+
+            fn call(&mut self, current: A, previous: B, delta: C) -> u8 {
+                (current.into() - previous.into() + delta.into()) as u8
+            }
+        }
+
+        let narrow: Narrow =
+            Wide { a: 10, b: 20 }.hmap3(Narrow { a: 3, b: 4 }, Wide { a: 1, b: 2 }, Delta);
+        assert_eq!(narrow, Narrow { a: 8, b: 18 });
+    }
+
+    #[test]
+    fn hunzip_inverts_hzip() {
+        #[derive(Generic)]
+        struct Pairs {
+            a: (i64, u8),
+            b: (i32, u8),
+        }
+
+        let zipped: Pairs = Wide { a: 1, b: 2 }.hzip(Narrow { a: 3, b: 4 });
+        let (wide, narrow): (Wide, Narrow) = zipped.hunzip();
+        assert_eq!((wide.a, wide.b), (1, 2));
+        assert_eq!(narrow, Narrow { a: 3, b: 4 });
+    }
+
+    #[test]
+    fn borrowing_traversal() {
+        struct Point {
+            x: i32,
+            y: i32,
+        }
+
+        impl<'a> ToRef<'a> for Point {
+            type Output = frunk::HList!(&'a i32, &'a i32);
+
+            fn to_ref(&'a self) -> Self::Output {
+                frunk::hlist![&self.x, &self.y]
+            }
+        }
+
+        struct Collect<'b>(&'b mut Vec<i32>);
+
+        impl Func<&i32> for Collect<'_> {
+            type Output = ();
+
+            fn call(&mut self, i: &i32) {
+                self.0.push(*i);
+            }
+        }
+
+        let point = Point { x: 1, y: 2 };
+        let mut seen = Vec::new();
+        point.for_each_ref(Collect(&mut seen));
+        assert_eq!(seen, vec![1, 2]);
+        assert_eq!(point.fields_iter::<i32>().sum::<i32>(), 3);
+        assert_eq!(frunk::hlist![3, 4].fields_iter::<i32>().max(), Some(&4));
+    }
+
+    #[test]
+    fn in_place_mutation() {
+        struct Names {
+            first: String,
+            last: String,
+        }
+
+        impl<'a> ToMut<'a> for Names {
+            type Output = frunk::HList!(&'a mut String, &'a mut String);
+
+            fn to_mut(&'a mut self) -> Self::Output {
+                frunk::hlist![&mut self.first, &mut self.last]
+            }
+        }
+
+        struct Trim;
+
+        impl Func<&mut String> for Trim {
+            type Output = ();
+
+            fn call(&mut self, i: &mut String) {
+                *i = i.trim().to_string();
+            }
+        }
+
+        let mut names = Names {
+            first: " Ada ".to_string(),
+            last: "Lovelace\n".to_string(),
+        };
+        names.for_each_mut(Trim);
+        assert_eq!(
+            (names.first.as_str(), names.last.as_str()),
+            ("Ada", "Lovelace")
+        );
+        for name in names.fields_iter_mut::<String>() {
+            name.make_ascii_uppercase();
+        }
+        assert_eq!(names.last, "LOVELACE");
+    }
+
+    #[test]
+    fn map_to_array() {
+        struct ToI64;
+
+        impl<T: Into<i64>> Func<T> for ToI64 {
+            type Output = i64;
+
+            fn call(&mut self, i: T) -> i64 {
+                i.into()
+            }
+        }
+
+        assert_eq!(Wide { a: 1, b: 2 }.map_to_array(ToI64), [1, 2]);
+    }
+
+    #[test]
+    fn uniform_fast_path() {
+        let mut sum = 0;
+        Narrow { a: 1, b: 2 }.for_each_uniform(|x: u8| sum += x);
+        assert_eq!(sum, 3);
+        let doubled: Narrow = Narrow { a: 1, b: 2 }.hmap_uniform(|x: u8| x * 2);
+        assert_eq!(doubled, Narrow { a: 2, b: 4 });
+    }
+
+    #[test]
+    fn map_accum() {
+        struct RunningTotal;
+
+        impl<T: Into<i64>> Scanner<i64, T> for RunningTotal {
+            type Output = i64;
+
+            fn scan(&mut self, acc: i64, i: T) -> (i64, i64) {
+                let total = acc + i.into();
+                (total, total)
+            }
+        }
+
+        #[derive(Generic, Debug, PartialEq)]
+        struct Totals {
+            a: i64,
+            b: i64,
+        }
+
+        let (totals, sum): (Totals, i64) = Wide { a: 3, b: 4 }.map_accum(10, RunningTotal);
+        assert_eq!(totals, Totals { a: 13, b: 17 });
+        assert_eq!(sum, 17);
+    }
+
+    #[test]
+    fn enumerate() {
+        struct Weight;
+
+        impl<T: Into<i64>> Func<(usize, T)> for Weight {
+            type Output = i64;
+
+            fn call(&mut self, (index, i): (usize, T)) -> i64 {
+                i.into() * (index as i64 + 1)
+            }
+        }
+
+        let weighted: Narrow =
+            Narrow { a: 5, b: 6 }.hmap_enumerate(FnPoly(|(i, x): (usize, u8)| x + i as u8));
+        assert_eq!(weighted, Narrow { a: 5, b: 7 });
+        let mut seen = Vec::new();
+        Wide { a: 5, b: 6 }.for_each_enumerate(Weight.map_output(|x| seen.push(x)));
+        assert_eq!(seen, [5, 12]);
+    }
+
+    #[test]
+    fn all_and_any() {
+        struct IsPositive<'a>(&'a mut usize);
+
+        impl<T: Into<i64>> Func<T> for IsPositive<'_> {
+            type Output = bool;
+
+            fn call(&mut self, i: T) -> bool {
+                *self.0 += 1;
+                i.into() > 0
+            }
+        }
+
+        let mut visited = 0;
+        assert!(Wide { a: 1, b: 2 }.all(IsPositive(&mut visited)));
+        assert_eq!(visited, 2);
+        visited = 0;
+        assert!(!Wide { a: -1, b: 2 }.all(IsPositive(&mut visited)));
+        assert_eq!(visited, 1);
+        visited = 0;
+        assert!(Wide { a: 1, b: -2 }.any(IsPositive(&mut visited)));
+        assert_eq!(visited, 1);
+        assert!(!Wide { a: 0, b: -2 }.any(IsPositive(&mut visited)));
+    }
+
+    #[test]
+    fn find_map_and_position() {
+        struct Negative;
+
+        impl<T: Into<i64>> Func<T> for Negative {
+            type Output = Option<i64>;
+
+            fn call(&mut self, i: T) -> Option<i64> {
+                Some(i.into()).filter(|&i| i < 0)
+            }
+        }
+
+        assert_eq!(Wide { a: 1, b: -2 }.find_map(Negative), Some(-2));
+        assert_eq!(Wide { a: 1, b: 2 }.find_map(Negative), None);
+        let is_negative = || Negative.map_output(|o: Option<i64>| o.is_some());
+        assert_eq!(Wide { a: 1, b: -2 }.position(is_negative()), Some(1));
+        assert_eq!(Wide { a: 1, b: 2 }.position(is_negative()), None);
+    }
+
+    #[test]
+    fn map_reduce() {
+        struct Widen;
+
+        impl<T: Into<i64>> Func<T> for Widen {
+            type Output = i64;
+
+            fn call(&mut self, i: T) -> i64 {
+                i.into()
+            }
+        }
+
+        assert_eq!(Wide { a: 3, b: 4 }.map_reduce(Widen, |x, y| x + y), Some(7));
+        assert_eq!(
+            Wide { a: 3, b: 4 }.map_reduce(Widen, |x, y| x - y),
+            Some(-1)
+        );
+        assert_eq!(HNil.map_reduce(Widen, |x: i64, y| x + y), None);
+    }
+
+    #[test]
+    fn fields_of_type() {
+        #[derive(Generic)]
+        struct Mixed {
+            a: u8,
+            b: String,
+            c: u8,
+            d: i8,
+        }
+
+        let mixed = frunk::hlist![1u8, "x".to_string(), 2u8, -3i8];
+        assert_eq!(mixed.fields_of_type_ref::<u8>(), [&1, &2]);
+        assert!(mixed.fields_of_type_ref::<u16>().is_empty());
+        let mixed: Mixed = Generic::from(mixed);
+        assert_eq!(mixed.fields_of_type::<String>(), ["x"]);
+    }
+
+    #[test]
+    fn replace_field() {
+        let wide = Wide { a: 1, b: 2 }.replace_field(FnPoly(|b: i32| b * 10));
+        assert_eq!((wide.a, wide.b), (1, 20));
+        let wide = wide.replace_field(FnPoly(|a: i64| -a));
+        assert_eq!((wide.a, wide.b), (-1, 20));
+    }
+
+    #[test]
+    fn field_count() {
+        const { assert!(Wide::FIELD_COUNT == 2) };
+    }
+
+    #[test]
+    fn map_to_list_len() {
+        fn expected_len<T: WithGeneric, F>() -> usize
+        where
+            T::Repr: MapToList<F, u8>,
+        {
+            <T::Repr as MapToList<F, u8>>::LEN
+        }
+
+        assert_eq!(expected_len::<Narrow, Identity>(), 2);
+        let list = Narrow { a: 1, b: 2 }.map_to_list(Identity);
+        assert_eq!(list.len(), 2);
+        assert!(!list.is_empty());
+    }
+
+    #[test]
+    fn transpose_options() {
+        #[derive(Generic)]
+        struct Partial {
+            a: Option<u8>,
+            b: Option<u8>,
+        }
+
+        let complete: Option<Narrow> = Partial {
+            a: Some(1),
+            b: Some(2),
+        }
+        .transpose_options();
+        assert_eq!(complete, Some(Narrow { a: 1, b: 2 }));
+        let complete: Option<Narrow> = Partial {
+            a: Some(1),
+            b: None,
+        }
+        .transpose_options();
+        assert_eq!(complete, None);
+    }
+
+    #[test]
+    fn transpose_results() {
+        #[derive(Generic)]
+        struct Parsed {
+            a: Result<u8, String>,
+            b: Result<u8, String>,
+        }
+
+        let ok = || Parsed { a: Ok(1), b: Ok(2) };
+        let failed = || Parsed {
+            a: Err("a".to_string()),
+            b: Err("b".to_string()),
+        };
+        assert_eq!(ok().transpose_results(), Ok(Narrow { a: 1, b: 2 }));
+        assert_eq!(ok().transpose_results_all(), Ok(Narrow { a: 1, b: 2 }));
+        assert_eq!(
+            failed().transpose_results::<Narrow, _>(),
+            Err("a".to_string())
+        );
+        assert_eq!(
+            failed().transpose_results_all::<Narrow, _>(),
+            Err(vec![(0, "a".to_string()), (1, "b".to_string())])
+        );
+    }
+
+    #[test]
+    fn zip_fields() {
+        #[derive(Generic)]
+        struct Columns<A, B> {
+            a: A,
+            b: B,
+        }
+
+        let columns = Columns {
+            a: vec![1, 2, 3].into_iter(),
+            b: vec![4, 5].into_iter(),
+        };
+        let rows = columns.zip_fields().collect::<Vec<Narrow>>();
+        assert_eq!(rows, [Narrow { a: 1, b: 4 }, Narrow { a: 2, b: 5 }]);
+    }
+
+    #[test]
+    fn for_each_rev() {
+        let mut seen = Vec::new();
+        Wide { a: 1, b: 2 }.for_each_rev(DebugFunc.map_output(|s| seen.push(s)));
+        assert_eq!(seen, ["2", "1"]);
+    }
+
+    #[test]
+    fn hfold_uses_context() {
+        struct WeightedSum {
+            weight: i64,
+        }
+
+        impl<T: Into<i64>> Folder<i64, T> for WeightedSum {
+            fn fold(&mut self, acc: i64, i: T) -> i64 {
+                let acc = acc + self.weight * i.into();
+                self.weight *= 10;
+                acc
+            }
+        }
+
+        let sum = Wide { a: 1, b: 2 }.hfold(0, WeightedSum { weight: 1 });
+        assert_eq!(sum, 21);
+    }
+
+    #[test]
+    fn try_map_to_list_drops_partial_results() {
+        use std::rc::Rc;
+
+        struct Track(Rc<()>);
+
+        impl<T: Into<i64>> TryFunc<T> for Track {
+            type Output = Rc<()>;
+            type Error = i64;
+
+            fn try_call(&mut self, i: T) -> Result<Rc<()>, i64> {
+                let i = i.into();
+                if i < 0 {
+                    Err(i)
+                } else {
+                    Ok(self.0.clone())
+                }
+            }
+        }
+
+        let counter = Rc::new(());
+        let result = Wide { a: 1, b: -2 }.try_map_to_list(Track(counter.clone()));
+        assert!(matches!(result, Err(-2)));
+        assert_eq!(Rc::strong_count(&counter), 1);
+
+        let list = Wide { a: 1, b: 2 }
+            .try_map_to_list(Track(counter.clone()))
+            .unwrap_or_else(|_| unreachable!());
+        assert_eq!(Rc::strong_count(&counter), 3);
+        drop(list);
+        assert_eq!(Rc::strong_count(&counter), 1);
     }
 }
@@ -0,0 +1,180 @@
+//! Traversals which run the [Func] for each field on a separate thread, for CPU-bound per-field work outside of an
+//! async runtime. Every field gets its own clone of the `Func`.
+
+use frunk::{prelude::HList, HCons, HNil};
+
+use crate::Func;
+
+/// Map every field of an HList through a [Func], each on its own [scoped thread](std::thread::scope). `'env` is the
+/// lifetime of the borrows that the fields and the `Func` are allowed to hold.
+pub trait ScopedMap<'env, F>: HList {
+    type Output: HList;
+
+    /// Spawn one thread per field onto `scope` and wait for all of them. If any thread panicked, fails with the
+    /// payload of the first one in declaration order.
+    fn scoped_map<'scope>(
+        self,
+        f: F,
+        scope: &'scope std::thread::Scope<'scope, 'env>,
+    ) -> std::thread::Result<Self::Output>;
+}
+
+impl<F> ScopedMap<'_, F> for HNil {
+    type Output = HNil;
+
+    fn scoped_map<'scope>(
+        self,
+        _f: F,
+        _scope: &'scope std::thread::Scope<'scope, '_>,
+    ) -> std::thread::Result<Self::Output> {
+        Ok(HNil)
+    }
+}
+
+impl<'env, F, Head: Send + 'env, Tail: ScopedMap<'env, F>> ScopedMap<'env, F> for HCons<Head, Tail>
+where
+    F: Func<Head> + Clone + Send + 'env,
+    F::Output: Send + 'env,
+{
+    type Output = HCons<F::Output, Tail::Output>;
+
+    fn scoped_map<'scope>(
+        self,
+        f: F,
+        scope: &'scope std::thread::Scope<'scope, 'env>,
+    ) -> std::thread::Result<Self::Output> {
+        let HCons { head, tail } = self;
+        let mut head_f = f.clone();
+        let handle = scope.spawn(move || head_f.call(head));
+        // Spawn the rest of the fields before waiting on this one. A panic in this field takes precedence over any in
+        // the later ones.
+        let tail = tail.scoped_map(f, scope);
+        let head = handle.join()?;
+        Ok(HCons { head, tail: tail? })
+    }
+}
+
+/// Map every field of an HList through a [Func] on the rayon thread pool
+#[cfg(feature = "rayon")]
+pub trait RayonMap<F>: HList {
+    type Output: HList;
+
+    fn rayon_map(self, f: F) -> Self::Output;
+}
+
+#[cfg(feature = "rayon")]
+impl<F> RayonMap<F> for HNil {
+    type Output = HNil;
+
+    fn rayon_map(self, _f: F) -> Self::Output {
+        HNil
+    }
+}
+
+#[cfg(feature = "rayon")]
+impl<F, Head: Send, Tail: RayonMap<F> + Send> RayonMap<F> for HCons<Head, Tail>
+where
+    F: Func<Head> + Clone + Send,
+    F::Output: Send,
+    Tail::Output: Send,
+{
+    type Output = HCons<F::Output, Tail::Output>;
+
+    fn rayon_map(self, f: F) -> Self::Output {
+        let HCons { head, tail } = self;
+        let mut head_f = f.clone();
+        let (head, tail) = rayon::join(move || head_f.call(head), move || tail.rayon_map(f));
+        HCons { head, tail }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use frunk::Generic;
+
+    use super::*;
+    use crate::WithGeneric;
+
+    #[derive(Generic, Debug, PartialEq)]
+    struct Pair<A, B> {
+        a: A,
+        b: B,
+    }
+
+    #[derive(Clone)]
+    struct Checksum;
+
+    impl Func<Vec<u8>> for Checksum {
+        type Output = u32;
+
+        fn call(&mut self, i: Vec<u8>) -> u32 {
+            i.into_iter().map(u32::from).sum()
+        }
+    }
+
+    impl Func<&'static str> for Checksum {
+        type Output = usize;
+
+        fn call(&mut self, i: &'static str) -> usize {
+            i.len()
+        }
+    }
+
+    #[test]
+    fn hmap_scoped_threads() {
+        let sums: Pair<u32, usize> = Pair {
+            a: vec![1, 2, 3],
+            b: "abcd",
+        }
+        .hmap_scoped_threads(Checksum);
+        assert_eq!(sums, Pair { a: 6, b: 4 });
+
+        // Neither the fields nor the Func need to be 'static
+        let (a, b, offset) = (1, 2, 10);
+        let shifted: Pair<u32, u32> =
+            Pair { a: &a, b: &b }.hmap_scoped_threads(crate::FnPoly(|x: &u32| x + offset));
+        assert_eq!(shifted, Pair { a: 11, b: 12 });
+    }
+
+    #[test]
+    fn hmap_scoped_threads_first_panic() {
+        #[derive(Clone)]
+        struct Fail;
+
+        impl Func<&'static str> for Fail {
+            type Output = ();
+
+            fn call(&mut self, i: &'static str) {
+                std::panic::panic_any(i)
+            }
+        }
+
+        let payload = std::panic::catch_unwind(|| {
+            let _: Pair<(), ()> = Pair {
+                a: "first",
+                b: "second",
+            }
+            .hmap_scoped_threads(Fail);
+        })
+        .unwrap_err();
+        assert_eq!(payload.downcast_ref::<&str>(), Some(&"first"));
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn par_hmap_rayon() {
+        let sums: Pair<u32, usize> = Pair {
+            a: vec![1, 2, 3],
+            b: "abcd",
+        }
+        .par_hmap_rayon(Checksum);
+        assert_eq!(sums, Pair { a: 6, b: 4 });
+
+        let seen = std::sync::Mutex::new(Vec::new());
+        Pair { a: 1, b: 2 }
+            .par_for_each_rayon(crate::FnPoly(|i: u32| seen.lock().unwrap().push(i)));
+        let mut seen = seen.into_inner().unwrap();
+        seen.sort();
+        assert_eq!(seen, [1, 2]);
+    }
+}
@@ -0,0 +1,154 @@
+//! Parsing a labelled struct from a row of strings, such as a line of CSV or TSV, with each field's [FromStr] impl
+
+use std::{error::Error, fmt, str::FromStr};
+
+use frunk::{
+    labelled::{field_with_name, Field},
+    prelude::HList,
+    HCons, HNil,
+};
+
+use crate::field_names::Label;
+
+/// Returned when a row of strings can't be parsed into a struct
+#[derive(Debug)]
+pub enum ParseError {
+    /// The row ended before the field at `index`
+    Missing { index: usize, field: &'static str },
+    /// The value for the field at `index` was rejected by its [FromStr] impl
+    Invalid {
+        index: usize,
+        field: &'static str,
+        source: Box<dyn Error + Send + Sync>,
+    },
+    /// The row has more values than the struct has fields
+    TooManyValues { expected: usize },
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::Missing { index, field } => {
+                write!(f, "missing value for field `{field}` at index {index}")
+            }
+            ParseError::Invalid {
+                index,
+                field,
+                source,
+            } => write!(
+                f,
+                "invalid value for field `{field}` at index {index}: {source}"
+            ),
+            ParseError::TooManyValues { expected } => {
+                write!(f, "expected {expected} values but found more")
+            }
+        }
+    }
+}
+
+impl Error for ParseError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ParseError::Invalid { source, .. } => Some(source.as_ref()),
+            _ => None,
+        }
+    }
+}
+
+/// Build a labelled HList by parsing one string per field
+pub trait ParseFields<'s>: HList {
+    /// Parse each field from the next value of `values`, stopping at the first one that is missing or invalid.
+    /// `index` is the position of the first field within the row.
+    fn parse_fields(
+        values: &mut impl Iterator<Item = &'s str>,
+        index: usize,
+    ) -> Result<Self, ParseError>;
+}
+
+impl<'s> ParseFields<'s> for HNil {
+    fn parse_fields(
+        _values: &mut impl Iterator<Item = &'s str>,
+        _index: usize,
+    ) -> Result<Self, ParseError> {
+        Ok(HNil)
+    }
+}
+
+impl<'s, Name: Label, T: FromStr, Tail: ParseFields<'s>> ParseFields<'s>
+    for HCons<Field<Name, T>, Tail>
+where
+    T::Err: Error + Send + Sync + 'static,
+{
+    fn parse_fields(
+        values: &mut impl Iterator<Item = &'s str>,
+        index: usize,
+    ) -> Result<Self, ParseError> {
+        let field = Name::NAME;
+        let value = values.next().ok_or(ParseError::Missing { index, field })?;
+        let value = value.parse().map_err(|err: T::Err| ParseError::Invalid {
+            index,
+            field,
+            source: Box::new(err),
+        })?;
+        Ok(HCons {
+            head: field_with_name(field, value),
+            tail: Tail::parse_fields(values, index + 1)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use frunk::LabelledGeneric;
+
+    use super::*;
+    use crate::WithLabelledGeneric;
+
+    #[derive(LabelledGeneric, Debug, PartialEq)]
+    struct Trade {
+        symbol: String,
+        quantity: u32,
+        price: f64,
+    }
+
+    #[test]
+    fn parse_row() {
+        let trade = Trade::parse_fields("ACME\t10\t2.5".split('\t')).unwrap();
+        assert_eq!(
+            trade,
+            Trade {
+                symbol: "ACME".to_string(),
+                quantity: 10,
+                price: 2.5,
+            }
+        );
+    }
+
+    #[test]
+    fn parse_errors() {
+        let err = Trade::parse_fields(["ACME", "ten", "2.5"]).unwrap_err();
+        assert!(matches!(
+            err,
+            ParseError::Invalid {
+                index: 1,
+                field: "quantity",
+                ..
+            }
+        ));
+        assert_eq!(
+            err.to_string(),
+            "invalid value for field `quantity` at index 1: invalid digit found in string"
+        );
+        assert!(matches!(
+            Trade::parse_fields(["ACME", "10"]),
+            Err(ParseError::Missing {
+                index: 2,
+                field: "price"
+            })
+        ));
+        assert!(matches!(
+            Trade::parse_fields(["ACME", "10", "2.5", "extra"]),
+            Err(ParseError::TooManyValues { expected: 3 })
+        ));
+    }
+}
@@ -0,0 +1,517 @@
+//! A [Partial] holds some subset of the fields of a labelled struct, each one as an [Option]. Partials can be layered
+//! on top of one another with [merge](Partial::merge) and then written onto an existing value or completed into a new
+//! one, which covers config layering and PATCH-style updates.
+
+use std::{fmt, marker::PhantomData};
+
+use frunk::{
+    labelled::{field_with_name, Field, IntoLabelledGeneric},
+    prelude::HList,
+    HCons, HNil, LabelledGeneric,
+};
+
+use crate::{field_names::Label, LabelledFunc};
+
+/// Maps the labelled HList repr of a struct to the repr of its struct-of-Options mirror
+pub trait Optionals: HList + Sized {
+    type Options: HList;
+
+    /// Every field absent
+    fn none() -> Self::Options;
+
+    /// Every field present
+    fn some(self) -> Self::Options;
+
+    /// Take each field from `rhs` if it's present there and from `lhs` otherwise
+    fn merge(lhs: Self::Options, rhs: Self::Options) -> Self::Options;
+
+    /// The complete HList, or `None` if any field is absent
+    fn complete(options: Self::Options) -> Option<Self>;
+}
+
+impl Optionals for HNil {
+    type Options = HNil;
+
+    fn none() -> Self::Options {
+        HNil
+    }
+
+    fn some(self) -> Self::Options {
+        HNil
+    }
+
+    fn merge(_lhs: Self::Options, _rhs: Self::Options) -> Self::Options {
+        HNil
+    }
+
+    fn complete(_options: Self::Options) -> Option<Self> {
+        Some(HNil)
+    }
+}
+
+impl<Name: Label, T, Tail: Optionals> Optionals for HCons<Field<Name, T>, Tail> {
+    type Options = HCons<Field<Name, Option<T>>, Tail::Options>;
+
+    fn none() -> Self::Options {
+        HCons {
+            head: field_with_name(Name::NAME, None),
+            tail: Tail::none(),
+        }
+    }
+
+    fn some(self) -> Self::Options {
+        HCons {
+            head: field_with_name(self.head.name, Some(self.head.value)),
+            tail: self.tail.some(),
+        }
+    }
+
+    fn merge(lhs: Self::Options, rhs: Self::Options) -> Self::Options {
+        HCons {
+            head: field_with_name(rhs.head.name, rhs.head.value.or(lhs.head.value)),
+            tail: Tail::merge(lhs.tail, rhs.tail),
+        }
+    }
+
+    fn complete(options: Self::Options) -> Option<Self> {
+        let head = options.head.value?;
+        Some(HCons {
+            head: field_with_name(options.head.name, head),
+            tail: Tail::complete(options.tail)?,
+        })
+    }
+}
+
+/// Write the present fields of a struct-of-Options repr through a labelled HList of mutable references
+pub trait Assign<Options>: HList {
+    fn assign(self, options: Options);
+}
+
+impl Assign<HNil> for HNil {
+    fn assign(self, _options: HNil) {}
+}
+
+impl<'a, Name, T: 'a, Tail: Assign<OptionsTail>, OptionsTail>
+    Assign<HCons<Field<Name, Option<T>>, OptionsTail>> for HCons<Field<Name, &'a mut T>, Tail>
+{
+    fn assign(self, options: HCons<Field<Name, Option<T>>, OptionsTail>) {
+        if let Some(value) = options.head.value {
+            *self.head.value = value;
+        }
+        self.tail.assign(options.tail)
+    }
+}
+
+/// Complete a struct-of-Options repr by calling `F` with the name and a type token for each absent field
+pub trait FillMissing<F>: HList {
+    type Output: HList;
+
+    fn fill_missing(self, f: F) -> Self::Output;
+}
+
+impl<F> FillMissing<F> for HNil {
+    type Output = HNil;
+
+    fn fill_missing(self, _f: F) -> Self::Output {
+        HNil
+    }
+}
+
+impl<F: LabelledFunc<PhantomData<T>, Output = T>, Name, T, Tail: FillMissing<F>> FillMissing<F>
+    for HCons<Field<Name, Option<T>>, Tail>
+{
+    type Output = HCons<Field<Name, T>, Tail::Output>;
+
+    fn fill_missing(self, mut f: F) -> Self::Output {
+        let HCons { head, tail } = self;
+        let value = match head.value {
+            Some(value) => value,
+            None => f.call(head.name, PhantomData),
+        };
+        HCons {
+            head: field_with_name(head.name, value),
+            tail: tail.fill_missing(f),
+        }
+    }
+}
+
+/// A [LabelledFunc] producing each field type's [Default] value, for use with [FillMissing]
+pub struct DefaultValue;
+
+impl<T: Default> LabelledFunc<PhantomData<T>> for DefaultValue {
+    type Output = T;
+
+    fn call(&mut self, _name: &'static str, _i: PhantomData<T>) -> Self::Output {
+        T::default()
+    }
+}
+
+/// Compare two labelled HLists of references field by field
+pub trait Diff: HList {
+    /// Push the name of every field whose values differ onto `changed`, in declaration order
+    fn diff(self, other: Self, changed: &mut Vec<&'static str>);
+}
+
+impl Diff for HNil {
+    fn diff(self, _other: Self, _changed: &mut Vec<&'static str>) {}
+}
+
+impl<'a, Name, T: PartialEq + 'a, Tail: Diff> Diff for HCons<Field<Name, &'a T>, Tail> {
+    fn diff(self, other: Self, changed: &mut Vec<&'static str>) {
+        if self.head.value != other.head.value {
+            changed.push(self.head.name);
+        }
+        self.tail.diff(other.tail, changed)
+    }
+}
+
+/// Compare a labelled HList of references against an owned labelled HList with the same fields, keeping the owned
+/// values that differ
+pub trait DiffPatch<Other>: HList {
+    type Options: HList;
+
+    fn diff_patch(self, other: Other) -> Self::Options;
+}
+
+impl DiffPatch<HNil> for HNil {
+    type Options = HNil;
+
+    fn diff_patch(self, _other: HNil) -> Self::Options {
+        HNil
+    }
+}
+
+impl<'a, Name, T: PartialEq + 'a, Tail: DiffPatch<OtherTail>, OtherTail>
+    DiffPatch<HCons<Field<Name, T>, OtherTail>> for HCons<Field<Name, &'a T>, Tail>
+{
+    type Options = HCons<Field<Name, Option<T>>, Tail::Options>;
+
+    fn diff_patch(self, other: HCons<Field<Name, T>, OtherTail>) -> Self::Options {
+        let value = other.head.value;
+        let changed = (*self.head.value != value).then_some(value);
+        HCons {
+            head: field_with_name(other.head.name, changed),
+            tail: self.tail.diff_patch(other.tail),
+        }
+    }
+}
+
+/// Some subset of the fields of `T`
+pub struct Partial<T: LabelledGeneric>
+where
+    T::Repr: Optionals,
+{
+    pub(crate) options: <T::Repr as Optionals>::Options,
+}
+
+impl<T: LabelledGeneric> Partial<T>
+where
+    T::Repr: Optionals,
+{
+    /// A partial with none of the fields present
+    pub fn new() -> Self {
+        Partial {
+            options: T::Repr::none(),
+        }
+    }
+
+    /// Read the fields from a struct with the same field names whose fields are `Option`s of `T`'s, such as a
+    /// deserialized PATCH body
+    pub fn from_options<U: LabelledGeneric<Repr = <T::Repr as Optionals>::Options>>(
+        options: U,
+    ) -> Self {
+        Partial {
+            options: options.into(),
+        }
+    }
+
+    /// The inverse of [from_options](Partial::from_options)
+    pub fn into_options<U: LabelledGeneric<Repr = <T::Repr as Optionals>::Options>>(self) -> U {
+        U::from(self.options)
+    }
+
+    /// Layer `other` on top of `self`: fields present in `other` win, and the rest are kept from `self`
+    pub fn merge(self, other: Self) -> Self {
+        Partial {
+            options: T::Repr::merge(self.options, other.options),
+        }
+    }
+
+    /// Overwrite the fields of `target` that are present, leaving the others as they are
+    pub fn apply_to<'a>(self, target: &'a mut T)
+    where
+        &'a mut T: IntoLabelledGeneric,
+        <&'a mut T as IntoLabelledGeneric>::Repr: Assign<<T::Repr as Optionals>::Options>,
+    {
+        IntoLabelledGeneric::into(target).assign(self.options)
+    }
+
+    /// The complete struct, or `None` if any field is absent
+    pub fn into_complete(self) -> Option<T> {
+        T::Repr::complete(self.options).map(T::from)
+    }
+
+    /// Complete the struct, taking each absent field's [Default] value
+    pub fn fill_defaults(self) -> T
+    where
+        <T::Repr as Optionals>::Options: FillMissing<DefaultValue, Output = T::Repr>,
+    {
+        self.fill_with(DefaultValue)
+    }
+
+    /// Complete the struct, calling `f` with the name of each absent field and a type token for it to produce a
+    /// fallback
+    pub fn fill_with<F>(self, f: F) -> T
+    where
+        <T::Repr as Optionals>::Options: FillMissing<F, Output = T::Repr>,
+    {
+        T::from(self.options.fill_missing(f))
+    }
+}
+
+impl<T: LabelledGeneric> Default for Partial<T>
+where
+    T::Repr: Optionals,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: LabelledGeneric> From<T> for Partial<T>
+where
+    T::Repr: Optionals,
+{
+    /// A partial with every field present
+    fn from(value: T) -> Self {
+        Partial {
+            options: value.into().some(),
+        }
+    }
+}
+
+impl<T: LabelledGeneric> Clone for Partial<T>
+where
+    T::Repr: Optionals<Options: Clone>,
+{
+    fn clone(&self) -> Self {
+        Partial {
+            options: self.options.clone(),
+        }
+    }
+}
+
+impl<T: LabelledGeneric> PartialEq for Partial<T>
+where
+    T::Repr: Optionals<Options: PartialEq>,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.options == other.options
+    }
+}
+
+impl<T: LabelledGeneric> fmt::Debug for Partial<T>
+where
+    T::Repr: Optionals<Options: fmt::Debug>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Partial")
+            .field("options", &self.options)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use frunk::LabelledGeneric;
+
+    use super::*;
+    use crate::WithLabelledGeneric;
+
+    #[derive(LabelledGeneric, Clone, Debug, PartialEq)]
+    struct Settings {
+        host: String,
+        port: u16,
+        verbose: bool,
+    }
+
+    #[derive(LabelledGeneric, Default)]
+    struct SettingsPatch {
+        host: Option<String>,
+        port: Option<u16>,
+        verbose: Option<bool>,
+    }
+
+    fn settings() -> Settings {
+        Settings {
+            host: "localhost".to_string(),
+            port: 80,
+            verbose: false,
+        }
+    }
+
+    #[test]
+    fn merge_is_right_biased() {
+        let base = Partial::from(settings());
+        let file = Partial::<Settings>::from_options(SettingsPatch {
+            port: Some(8080),
+            ..Default::default()
+        });
+        let env = Partial::<Settings>::from_options(SettingsPatch {
+            port: Some(9090),
+            verbose: Some(true),
+            ..Default::default()
+        });
+        let merged = base.merge(file).merge(env);
+        assert_eq!(
+            merged.into_complete(),
+            Some(Settings {
+                host: "localhost".to_string(),
+                port: 9090,
+                verbose: true,
+            })
+        );
+    }
+
+    #[test]
+    fn apply_to() {
+        let mut target = settings();
+        let patch = Partial::<Settings>::from_options(SettingsPatch {
+            host: Some("example.com".to_string()),
+            ..Default::default()
+        });
+        patch.apply_to(&mut target);
+        assert_eq!(target.host, "example.com");
+        assert_eq!(target.port, 80);
+    }
+
+    #[test]
+    fn into_complete_missing_field() {
+        let partial = Partial::<Settings>::from_options(SettingsPatch {
+            host: Some("example.com".to_string()),
+            port: Some(443),
+            verbose: None,
+        });
+        assert_eq!(partial.clone().into_complete(), None);
+        let patch: SettingsPatch = partial.into_options();
+        assert_eq!(patch.port, Some(443));
+        assert_eq!(Partial::<Settings>::new(), Partial::default());
+    }
+
+    #[test]
+    fn fill_defaults_from_options_struct() {
+        let patch = SettingsPatch {
+            verbose: Some(true),
+            ..Default::default()
+        };
+        let settings: Settings = patch.fill_defaults();
+        assert_eq!(settings.port, 0);
+        assert!(settings.verbose);
+    }
+
+    #[test]
+    fn fill_defaults() {
+        let partial = Partial::<Settings>::from_options(SettingsPatch {
+            port: Some(443),
+            ..Default::default()
+        });
+        assert_eq!(
+            partial.fill_defaults(),
+            Settings {
+                host: String::new(),
+                port: 443,
+                verbose: false,
+            }
+        );
+    }
+
+    #[test]
+    fn diff() {
+        let before = settings();
+        let after = Settings {
+            port: 8080,
+            verbose: true,
+            ..settings()
+        };
+        assert_eq!(before.diff(&after), ["port", "verbose"]);
+        assert!(before.diff(&before.clone()).is_empty());
+
+        let patch = before.diff_patch(after.clone());
+        let options: SettingsPatch = patch.clone().into_options();
+        assert_eq!(options.host, None);
+        assert_eq!(options.port, Some(8080));
+        let mut patched = before;
+        patch.apply_to(&mut patched);
+        assert_eq!(patched, after);
+    }
+
+    struct Overrides<'a>(&'a [(&'static str, &'static str)]);
+
+    impl<T: std::str::FromStr> LabelledFunc<T> for Overrides<'_> {
+        type Output = T;
+
+        fn call(&mut self, name: &'static str, i: T) -> Self::Output {
+            match self.0.iter().find(|(field, _)| *field == name) {
+                Some((_, value)) => value
+                    .parse()
+                    .unwrap_or_else(|_| panic!("invalid value for `{name}`")),
+                None => i,
+            }
+        }
+    }
+
+    #[test]
+    fn build_default_with() {
+        let settings = Settings::build_default_with(Overrides(&[("port", "8080")]));
+        assert_eq!(
+            settings,
+            Settings {
+                host: String::new(),
+                port: 8080,
+                verbose: false,
+            }
+        );
+    }
+
+    struct Fallbacks;
+
+    impl LabelledFunc<PhantomData<String>> for Fallbacks {
+        type Output = String;
+
+        fn call(&mut self, name: &'static str, _i: PhantomData<String>) -> Self::Output {
+            format!("<{name}>")
+        }
+    }
+
+    impl LabelledFunc<PhantomData<u16>> for Fallbacks {
+        type Output = u16;
+
+        fn call(&mut self, _name: &'static str, _i: PhantomData<u16>) -> Self::Output {
+            80
+        }
+    }
+
+    impl LabelledFunc<PhantomData<bool>> for Fallbacks {
+        type Output = bool;
+
+        fn call(&mut self, _name: &'static str, _i: PhantomData<bool>) -> Self::Output {
+            true
+        }
+    }
+
+    #[test]
+    fn fill_with() {
+        let partial = Partial::<Settings>::from_options(SettingsPatch {
+            verbose: Some(false),
+            ..Default::default()
+        });
+        assert_eq!(
+            partial.fill_with(Fallbacks),
+            Settings {
+                host: "<host>".to_string(),
+                port: 80,
+                verbose: false,
+            }
+        );
+    }
+}
@@ -0,0 +1,184 @@
+//! Filling a labelled struct from a database row, or anything else that can look values up by column name. Each field
+//! is read by a [TryFunc] that receives the row, the field's name, and a token for the field's type, so the lookup
+//! can be delegated to whatever typed getter the database library provides.
+
+use std::{error::Error, fmt, marker::PhantomData};
+
+use frunk::{
+    labelled::{field_with_name, Field},
+    prelude::HList,
+    HCons, HNil,
+};
+
+use crate::{field_names::Label, TryFunc};
+
+/// A request for the column named after a field, passed to the [TryFunc] reading the field of type `T`
+pub struct Column<'r, R: ?Sized, T> {
+    pub row: &'r R,
+    pub name: &'static str,
+    marker: PhantomData<fn() -> T>,
+}
+
+/// A row whose columns can be read by name as values of type `T`. Implement this for a database library's row type
+/// (usually as a blanket impl over the types it can decode) to fill structs from it with [GetColumn].
+pub trait RowGet<T> {
+    type Error;
+
+    fn get(&self, name: &str) -> Result<T, Self::Error>;
+}
+
+/// A [TryFunc] reading each field from the column of the same name through [RowGet]
+pub struct GetColumn;
+
+impl<R: RowGet<T> + ?Sized, T> TryFunc<Column<'_, R, T>> for GetColumn {
+    type Output = T;
+    type Error = R::Error;
+
+    fn try_call(&mut self, i: Column<'_, R, T>) -> Result<Self::Output, Self::Error> {
+        i.row.get(i.name)
+    }
+}
+
+/// Returned when a field can't be read from a row, naming the column that failed
+#[derive(Debug, PartialEq, Eq)]
+pub struct ColumnError<E> {
+    pub column: &'static str,
+    pub source: E,
+}
+
+impl<E: fmt::Display> fmt::Display for ColumnError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "failed to read column `{}`: {}",
+            self.column, self.source
+        )
+    }
+}
+
+impl<E: Error + 'static> Error for ColumnError<E> {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&self.source)
+    }
+}
+
+/// Fill a labelled HList from a row by calling `F` once per field in declaration order
+pub trait FromRow<R: ?Sized, F, E>: HList {
+    /// Read each field, stopping at the first column that is missing or can't be converted
+    fn from_row(row: &R, f: F) -> Result<Self, ColumnError<E>>;
+}
+
+impl<R: ?Sized, F, E> FromRow<R, F, E> for HNil {
+    fn from_row(_row: &R, _f: F) -> Result<Self, ColumnError<E>> {
+        Ok(HNil)
+    }
+}
+
+impl<
+        R: ?Sized,
+        F: for<'r> TryFunc<Column<'r, R, T>, Output = T, Error = E>,
+        E,
+        Name: Label,
+        T,
+        Tail: FromRow<R, F, E>,
+    > FromRow<R, F, E> for HCons<Field<Name, T>, Tail>
+{
+    fn from_row(row: &R, mut f: F) -> Result<Self, ColumnError<E>> {
+        let name = Name::NAME;
+        let value = f
+            .try_call(Column {
+                row,
+                name,
+                marker: PhantomData,
+            })
+            .map_err(|source| ColumnError {
+                column: name,
+                source,
+            })?;
+        Ok(HCons {
+            head: field_with_name(name, value),
+            tail: Tail::from_row(row, f)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{any::Any, collections::HashMap};
+
+    use frunk::LabelledGeneric;
+
+    use super::*;
+    use crate::WithLabelledGeneric;
+
+    /// Stands in for a database row: dynamically typed values keyed by column name
+    struct DynRow(HashMap<&'static str, Box<dyn Any>>);
+
+    #[derive(Debug, PartialEq)]
+    enum DynRowError {
+        NotFound,
+        WrongType,
+    }
+
+    impl<T: Clone + 'static> RowGet<T> for DynRow {
+        type Error = DynRowError;
+
+        fn get(&self, name: &str) -> Result<T, Self::Error> {
+            let value = self.0.get(name).ok_or(DynRowError::NotFound)?;
+            value
+                .downcast_ref::<T>()
+                .cloned()
+                .ok_or(DynRowError::WrongType)
+        }
+    }
+
+    #[derive(LabelledGeneric, Debug, PartialEq)]
+    struct User {
+        id: i64,
+        name: String,
+    }
+
+    fn row(entries: Vec<(&'static str, Box<dyn Any>)>) -> DynRow {
+        DynRow(entries.into_iter().collect())
+    }
+
+    #[test]
+    fn from_row() {
+        let user: User = WithLabelledGeneric::from_row(
+            &row(vec![
+                ("name", Box::new("ada".to_string())),
+                ("id", Box::new(7i64)),
+                ("email", Box::new("ada@example.com".to_string())),
+            ]),
+            GetColumn,
+        )
+        .unwrap();
+        assert_eq!(
+            user,
+            User {
+                id: 7,
+                name: "ada".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn from_row_errors() {
+        let missing = User::from_row(&row(vec![("id", Box::new(7i64))]), GetColumn);
+        assert_eq!(
+            missing,
+            Err(ColumnError {
+                column: "name",
+                source: DynRowError::NotFound,
+            })
+        );
+        let ill_typed = User::from_row(&row(vec![("id", Box::new(7i32))]), GetColumn);
+        assert_eq!(
+            ill_typed,
+            Err(ColumnError {
+                column: "id",
+                source: DynRowError::WrongType,
+            })
+        );
+    }
+}
@@ -0,0 +1,353 @@
+//! Traversals over structs whose fields are locks ([Mutex]es or [RwLock]s). These go through the by-reference
+//! [LabelledGeneric] impls generated by frunk's derive, so a struct of locks needs `#[derive(LabelledGeneric)]` and the
+//! struct of guards it produces needs the same field names.
+//!
+//! [LabelledGeneric]: frunk::LabelledGeneric
+
+use std::{
+    error::Error,
+    fmt,
+    sync::{Mutex, MutexGuard, RwLock, RwLockReadGuard, RwLockWriteGuard},
+};
+
+use frunk::{
+    labelled::{field_with_name, Field},
+    prelude::HList,
+    HCons, HNil,
+};
+
+/// Returned when one of the locks in a struct is poisoned, naming the field it belongs to
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Poisoned(pub &'static str);
+
+impl fmt::Display for Poisoned {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "lock for field `{}` is poisoned", self.0)
+    }
+}
+
+impl Error for Poisoned {}
+
+/// Lock every field of a labelled HList of references to [Mutex]es
+pub trait LockAll<'a>: HList {
+    type Guards: HList;
+
+    /// Acquire the locks in declaration order, releasing those already held if one of them is poisoned
+    fn lock_all(self) -> Result<Self::Guards, Poisoned>;
+}
+
+impl LockAll<'_> for HNil {
+    type Guards = HNil;
+
+    fn lock_all(self) -> Result<Self::Guards, Poisoned> {
+        Ok(HNil)
+    }
+}
+
+impl<'a, Name, T: 'a, Tail: LockAll<'a>> LockAll<'a> for HCons<Field<Name, &'a Mutex<T>>, Tail> {
+    type Guards = HCons<Field<Name, MutexGuard<'a, T>>, Tail::Guards>;
+
+    fn lock_all(self) -> Result<Self::Guards, Poisoned> {
+        let guard = self
+            .head
+            .value
+            .lock()
+            .map_err(|_| Poisoned(self.head.name))?;
+        Ok(HCons {
+            head: field_with_name(self.head.name, guard),
+            tail: self.tail.lock_all()?,
+        })
+    }
+}
+
+/// Acquire every field of a labelled HList of references to [RwLock]s, either for reading or for writing
+pub trait LockAllRw<'a>: HList {
+    type ReadGuards: HList;
+    type WriteGuards: HList;
+
+    /// Acquire shared access in declaration order, releasing the guards already held if a lock is poisoned
+    fn read_all(self) -> Result<Self::ReadGuards, Poisoned>;
+
+    /// Acquire exclusive access in declaration order, releasing the guards already held if a lock is poisoned
+    fn write_all(self) -> Result<Self::WriteGuards, Poisoned>;
+}
+
+impl LockAllRw<'_> for HNil {
+    type ReadGuards = HNil;
+    type WriteGuards = HNil;
+
+    fn read_all(self) -> Result<Self::ReadGuards, Poisoned> {
+        Ok(HNil)
+    }
+
+    fn write_all(self) -> Result<Self::WriteGuards, Poisoned> {
+        Ok(HNil)
+    }
+}
+
+impl<'a, Name, T: 'a, Tail: LockAllRw<'a>> LockAllRw<'a>
+    for HCons<Field<Name, &'a RwLock<T>>, Tail>
+{
+    type ReadGuards = HCons<Field<Name, RwLockReadGuard<'a, T>>, Tail::ReadGuards>;
+    type WriteGuards = HCons<Field<Name, RwLockWriteGuard<'a, T>>, Tail::WriteGuards>;
+
+    fn read_all(self) -> Result<Self::ReadGuards, Poisoned> {
+        let guard = self
+            .head
+            .value
+            .read()
+            .map_err(|_| Poisoned(self.head.name))?;
+        Ok(HCons {
+            head: field_with_name(self.head.name, guard),
+            tail: self.tail.read_all()?,
+        })
+    }
+
+    fn write_all(self) -> Result<Self::WriteGuards, Poisoned> {
+        let guard = self
+            .head
+            .value
+            .write()
+            .map_err(|_| Poisoned(self.head.name))?;
+        Ok(HCons {
+            head: field_with_name(self.head.name, guard),
+            tail: self.tail.write_all()?,
+        })
+    }
+}
+
+/// Lock every field of a labelled HList of references to [tokio::sync::Mutex]es
+#[cfg(feature = "tokio")]
+pub trait LockAllAsync<'a>: HList {
+    type Guards: HList;
+
+    /// Acquire the locks one after another in declaration order
+    fn lock_all_async(self) -> impl std::future::Future<Output = Self::Guards>;
+}
+
+#[cfg(feature = "tokio")]
+impl LockAllAsync<'_> for HNil {
+    type Guards = HNil;
+
+    async fn lock_all_async(self) -> Self::Guards {
+        HNil
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl<'a, Name, T: 'a, Tail: LockAllAsync<'a>> LockAllAsync<'a>
+    for HCons<Field<Name, &'a tokio::sync::Mutex<T>>, Tail>
+{
+    type Guards = HCons<Field<Name, tokio::sync::MutexGuard<'a, T>>, Tail::Guards>;
+
+    async fn lock_all_async(self) -> Self::Guards {
+        let guard = self.head.value.lock().await;
+        HCons {
+            head: field_with_name(self.head.name, guard),
+            tail: self.tail.lock_all_async().await,
+        }
+    }
+}
+
+/// Acquire every field of a labelled HList of references to [tokio::sync::RwLock]s, either for reading or for writing
+#[cfg(feature = "tokio")]
+pub trait LockAllRwAsync<'a>: HList {
+    type ReadGuards: HList;
+    type WriteGuards: HList;
+
+    /// Acquire shared access one lock after another in declaration order
+    fn read_all_async(self) -> impl std::future::Future<Output = Self::ReadGuards>;
+
+    /// Acquire exclusive access one lock after another in declaration order
+    fn write_all_async(self) -> impl std::future::Future<Output = Self::WriteGuards>;
+}
+
+#[cfg(feature = "tokio")]
+impl LockAllRwAsync<'_> for HNil {
+    type ReadGuards = HNil;
+    type WriteGuards = HNil;
+
+    async fn read_all_async(self) -> Self::ReadGuards {
+        HNil
+    }
+
+    async fn write_all_async(self) -> Self::WriteGuards {
+        HNil
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl<'a, Name, T: 'a, Tail: LockAllRwAsync<'a>> LockAllRwAsync<'a>
+    for HCons<Field<Name, &'a tokio::sync::RwLock<T>>, Tail>
+{
+    type ReadGuards = HCons<Field<Name, tokio::sync::RwLockReadGuard<'a, T>>, Tail::ReadGuards>;
+    type WriteGuards = HCons<Field<Name, tokio::sync::RwLockWriteGuard<'a, T>>, Tail::WriteGuards>;
+
+    async fn read_all_async(self) -> Self::ReadGuards {
+        let guard = self.head.value.read().await;
+        HCons {
+            head: field_with_name(self.head.name, guard),
+            tail: self.tail.read_all_async().await,
+        }
+    }
+
+    async fn write_all_async(self) -> Self::WriteGuards {
+        let guard = self.head.value.write().await;
+        HCons {
+            head: field_with_name(self.head.name, guard),
+            tail: self.tail.write_all_async().await,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{sync::Arc, thread};
+
+    use frunk::LabelledGeneric;
+
+    use super::*;
+    use crate::WithLabelledGeneric;
+
+    #[derive(LabelledGeneric, Default)]
+    struct Counters {
+        hits: Mutex<u32>,
+        names: Mutex<Vec<String>>,
+    }
+
+    #[derive(LabelledGeneric)]
+    struct CounterGuards<'a> {
+        hits: MutexGuard<'a, u32>,
+        names: MutexGuard<'a, Vec<String>>,
+    }
+
+    #[test]
+    fn lock_all() {
+        let counters = Counters::default();
+        {
+            let mut guards: CounterGuards = counters.lock_all().unwrap();
+            *guards.hits += 1;
+            guards.names.push("a".to_string());
+        }
+        assert_eq!(*counters.hits.lock().unwrap(), 1);
+        assert_eq!(*counters.names.lock().unwrap(), ["a"]);
+    }
+
+    #[test]
+    fn lock_all_poisoned() {
+        let counters = Arc::new(Counters::default());
+        let poisoner = Arc::clone(&counters);
+        thread::spawn(move || {
+            let _guard = poisoner.names.lock().unwrap();
+            panic!("poison the lock");
+        })
+        .join()
+        .unwrap_err();
+        let result = counters.lock_all::<CounterGuards>();
+        assert_eq!(result.err(), Some(Poisoned("names")));
+        // The guard acquired before the failure has been released
+        assert!(counters.hits.try_lock().is_ok());
+    }
+
+    #[derive(LabelledGeneric)]
+    struct Config {
+        retries: RwLock<u32>,
+        host: RwLock<String>,
+    }
+
+    #[derive(LabelledGeneric)]
+    struct ConfigSnapshot<'a> {
+        retries: RwLockReadGuard<'a, u32>,
+        host: RwLockReadGuard<'a, String>,
+    }
+
+    #[derive(LabelledGeneric)]
+    struct ConfigUpdate<'a> {
+        retries: RwLockWriteGuard<'a, u32>,
+        host: RwLockWriteGuard<'a, String>,
+    }
+
+    #[test]
+    fn read_all_write_all() {
+        let config = Config {
+            retries: RwLock::new(3),
+            host: RwLock::new("localhost".to_string()),
+        };
+        {
+            let mut update: ConfigUpdate = config.write_all().unwrap();
+            *update.retries = 5;
+            update.host.push_str(":80");
+        }
+        let first: ConfigSnapshot = config.read_all().unwrap();
+        let second: ConfigSnapshot = config.read_all().unwrap();
+        assert_eq!(*first.retries, 5);
+        assert_eq!(*second.host, "localhost:80");
+        assert!(config.retries.try_write().is_err());
+    }
+
+    #[cfg(feature = "tokio")]
+    #[derive(LabelledGeneric, Default)]
+    struct AsyncCounters {
+        hits: tokio::sync::Mutex<u32>,
+        names: tokio::sync::Mutex<Vec<String>>,
+    }
+
+    #[cfg(feature = "tokio")]
+    #[derive(LabelledGeneric)]
+    struct AsyncCounterGuards<'a> {
+        hits: tokio::sync::MutexGuard<'a, u32>,
+        names: tokio::sync::MutexGuard<'a, Vec<String>>,
+    }
+
+    #[cfg(feature = "tokio")]
+    #[tokio::test]
+    async fn lock_all_async() {
+        let counters = AsyncCounters::default();
+        {
+            let mut guards: AsyncCounterGuards = counters.lock_all_async().await;
+            *guards.hits += 2;
+            guards.names.push("b".to_string());
+        }
+        assert_eq!(*counters.hits.lock().await, 2);
+        assert_eq!(*counters.names.lock().await, ["b"]);
+    }
+
+    #[cfg(feature = "tokio")]
+    #[derive(LabelledGeneric)]
+    struct AsyncConfig {
+        retries: tokio::sync::RwLock<u32>,
+        host: tokio::sync::RwLock<String>,
+    }
+
+    #[cfg(feature = "tokio")]
+    #[derive(LabelledGeneric)]
+    struct AsyncConfigSnapshot<'a> {
+        retries: tokio::sync::RwLockReadGuard<'a, u32>,
+        host: tokio::sync::RwLockReadGuard<'a, String>,
+    }
+
+    #[cfg(feature = "tokio")]
+    #[derive(LabelledGeneric)]
+    struct AsyncConfigUpdate<'a> {
+        retries: tokio::sync::RwLockWriteGuard<'a, u32>,
+        host: tokio::sync::RwLockWriteGuard<'a, String>,
+    }
+
+    #[cfg(feature = "tokio")]
+    #[tokio::test]
+    async fn read_all_write_all_async() {
+        let config = AsyncConfig {
+            retries: tokio::sync::RwLock::new(3),
+            host: tokio::sync::RwLock::new("localhost".to_string()),
+        };
+        {
+            let mut update: AsyncConfigUpdate = config.write_all_async().await;
+            *update.retries = 5;
+            update.host.push_str(":80");
+        }
+        let snapshot: AsyncConfigSnapshot = config.read_all_async().await;
+        assert_eq!(*snapshot.retries, 5);
+        assert_eq!(*snapshot.host, "localhost:80");
+        assert!(config.retries.try_write().is_err());
+    }
+}
@@ -0,0 +1,227 @@
+//! A variant of frunk's [Transmogrifier](frunk::labelled::Transmogrifier) that runs a contextful [Func] on every leaf
+//! value while recursively restructuring labelled structs by field name.
+//!
+//! The leaf case applies when `F` implements [Func] for the source value with the target value as its output. `Option`,
+//! `Vec` and `Box` fields are converted element by element. Since those cases recurse into their contents, a `Func`
+//! that also accepts the container types themselves makes the conversion ambiguous, so `F` should only be implemented
+//! for the leaf types it converts.
+
+use frunk::{
+    indices::{
+        DoTransmog, IdentityTransMog, LabelledGenericTransmogIndicesWrapper, MappingIndicesWrapper,
+        PluckedLabelledGenericIndicesWrapper,
+    },
+    labelled::{field_with_name, ByNameFieldPlucker, Field},
+    HCons, HNil, LabelledGeneric,
+};
+
+use crate::Func;
+
+pub trait TransmogrifyWith<Target, F, Indices> {
+    fn transmogrify_with(self, f: &mut F) -> Target;
+}
+
+impl<Key, Source, F: Func<Source>> TransmogrifyWith<F::Output, F, IdentityTransMog>
+    for Field<Key, Source>
+{
+    fn transmogrify_with(self, f: &mut F) -> F::Output {
+        f.call(self.value)
+    }
+}
+
+impl<Key, Source, Target, F, InnerIndices>
+    TransmogrifyWith<Vec<Target>, F, MappingIndicesWrapper<InnerIndices>>
+    for Field<Key, Vec<Source>>
+where
+    Field<Key, Source>: TransmogrifyWith<Target, F, InnerIndices>,
+{
+    fn transmogrify_with(self, f: &mut F) -> Vec<Target> {
+        let name = self.name;
+        self.value
+            .into_iter()
+            .map(|e| field_with_name::<Key, _>(name, e).transmogrify_with(f))
+            .collect()
+    }
+}
+
+impl<Key, Source, Target, F, InnerIndices>
+    TransmogrifyWith<Option<Target>, F, MappingIndicesWrapper<InnerIndices>>
+    for Field<Key, Option<Source>>
+where
+    Field<Key, Source>: TransmogrifyWith<Target, F, InnerIndices>,
+{
+    fn transmogrify_with(self, f: &mut F) -> Option<Target> {
+        let name = self.name;
+        self.value
+            .map(|e| field_with_name::<Key, _>(name, e).transmogrify_with(f))
+    }
+}
+
+impl<Key, Source, Target, F, InnerIndices>
+    TransmogrifyWith<Box<Target>, F, MappingIndicesWrapper<InnerIndices>>
+    for Field<Key, Box<Source>>
+where
+    Field<Key, Source>: TransmogrifyWith<Target, F, InnerIndices>,
+{
+    fn transmogrify_with(self, f: &mut F) -> Box<Target> {
+        Box::new(field_with_name::<Key, _>(self.name, *self.value).transmogrify_with(f))
+    }
+}
+
+impl<F> TransmogrifyWith<HNil, F, HNil> for HNil {
+    fn transmogrify_with(self, _f: &mut F) -> HNil {
+        HNil
+    }
+}
+
+impl<SourceHead, SourceTail, F> TransmogrifyWith<HNil, F, HNil> for HCons<SourceHead, SourceTail> {
+    fn transmogrify_with(self, _f: &mut F) -> HNil {
+        HNil
+    }
+}
+
+impl<SourceHead, SourceTail, TargetName, TargetHead, TargetTail, F, HeadIndex, TailIndices>
+    TransmogrifyWith<HCons<TargetHead, TargetTail>, F, HCons<HeadIndex, TailIndices>>
+    for Field<TargetName, HCons<SourceHead, SourceTail>>
+where
+    HCons<SourceHead, SourceTail>:
+        TransmogrifyWith<HCons<TargetHead, TargetTail>, F, HCons<HeadIndex, TailIndices>>,
+{
+    fn transmogrify_with(self, f: &mut F) -> HCons<TargetHead, TargetTail> {
+        self.value.transmogrify_with(f)
+    }
+}
+
+impl<SourceHead, SourceTail, TargetHeadName, TargetHeadValue, TargetTail, F, PluckIndex, HeadIndices, TailIndices>
+    TransmogrifyWith<
+        HCons<Field<TargetHeadName, TargetHeadValue>, TargetTail>,
+        F,
+        HCons<DoTransmog<PluckIndex, HeadIndices>, TailIndices>,
+    > for HCons<SourceHead, SourceTail>
+where
+    HCons<SourceHead, SourceTail>: ByNameFieldPlucker<TargetHeadName, PluckIndex>,
+    Field<
+        TargetHeadName,
+        <HCons<SourceHead, SourceTail> as ByNameFieldPlucker<TargetHeadName, PluckIndex>>::TargetValue,
+    >: TransmogrifyWith<TargetHeadValue, F, HeadIndices>,
+    <HCons<SourceHead, SourceTail> as ByNameFieldPlucker<TargetHeadName, PluckIndex>>::Remainder:
+        TransmogrifyWith<TargetTail, F, TailIndices>,
+{
+    fn transmogrify_with(self, f: &mut F) -> HCons<Field<TargetHeadName, TargetHeadValue>, TargetTail> {
+        let (head, remainder) = self.pluck_by_name();
+        let name = head.name;
+        HCons {
+            head: field_with_name(name, head.transmogrify_with(f)),
+            tail: remainder.transmogrify_with(f),
+        }
+    }
+}
+
+impl<Source: LabelledGeneric, Target: LabelledGeneric, F, Indices>
+    TransmogrifyWith<Target, F, LabelledGenericTransmogIndicesWrapper<Indices>> for Source
+where
+    Source::Repr: TransmogrifyWith<Target::Repr, F, Indices>,
+{
+    fn transmogrify_with(self, f: &mut F) -> Target {
+        LabelledGeneric::from(LabelledGeneric::into(self).transmogrify_with(f))
+    }
+}
+
+impl<Source, TargetName, TargetValue, F, Indices>
+    TransmogrifyWith<TargetValue, F, PluckedLabelledGenericIndicesWrapper<Indices>>
+    for Field<TargetName, Source>
+where
+    Source: LabelledGeneric + TransmogrifyWith<TargetValue, F, Indices>,
+    TargetValue: LabelledGeneric,
+{
+    fn transmogrify_with(self, f: &mut F) -> TargetValue {
+        self.value.transmogrify_with(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use frunk::LabelledGeneric;
+
+    use crate::{Func, WithLabelledGeneric};
+
+    #[derive(LabelledGeneric)]
+    struct SourceInner {
+        height: f64,
+        width: f64,
+    }
+
+    #[derive(LabelledGeneric)]
+    struct Source {
+        id: u32,
+        inner: SourceInner,
+        tags: Vec<u32>,
+        parent: Option<Box<SourceInner>>,
+    }
+
+    #[derive(LabelledGeneric, Debug, PartialEq)]
+    struct TargetInner {
+        width: f64,
+        height: f64,
+    }
+
+    #[derive(LabelledGeneric, Debug, PartialEq)]
+    struct Target {
+        tags: Vec<u64>,
+        inner: TargetInner,
+        id: u64,
+        parent: Option<Box<TargetInner>>,
+    }
+
+    struct Convert {
+        id_offset: u64,
+    }
+
+    impl Func<u32> for Convert {
+        type Output = u64;
+
+        fn call(&mut self, i: u32) -> u64 {
+            u64::from(i) + self.id_offset
+        }
+    }
+
+    impl Func<f64> for Convert {
+        type Output = f64;
+
+        fn call(&mut self, i: f64) -> f64 {
+            i * 100.0
+        }
+    }
+
+    #[test]
+    fn transmogrify_with() {
+        let source = Source {
+            id: 1,
+            inner: SourceInner {
+                height: 2.0,
+                width: 3.0,
+            },
+            tags: vec![4, 5],
+            parent: Some(Box::new(SourceInner {
+                height: 0.5,
+                width: 0.25,
+            })),
+        };
+        let target: Target = source.transmogrify_with(Convert { id_offset: 1000 });
+        assert_eq!(
+            target,
+            Target {
+                tags: vec![1004, 1005],
+                inner: TargetInner {
+                    width: 300.0,
+                    height: 200.0
+                },
+                id: 1001,
+                parent: Some(Box::new(TargetInner {
+                    width: 25.0,
+                    height: 50.0
+                })),
+            }
+        );
+    }
+}
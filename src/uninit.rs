@@ -0,0 +1,138 @@
+//! Mapping the fields of an HList straight into caller-provided storage, such as an arena or a preallocated buffer,
+//! without going through a [ConsList](crate::ConsList)
+
+use std::{
+    fmt,
+    mem::{ManuallyDrop, MaybeUninit},
+    ops::{Deref, DerefMut},
+};
+
+use frunk::{prelude::HList, HCons, HNil};
+
+use crate::Func;
+
+/// The initialized prefix of a buffer of [MaybeUninit]s. Dropping it drops the elements that have been written.
+pub struct InitSlice<'b, U> {
+    buf: &'b mut [MaybeUninit<U>],
+    len: usize,
+}
+
+impl<'b, U> InitSlice<'b, U> {
+    pub fn new(buf: &'b mut [MaybeUninit<U>]) -> Self {
+        InitSlice { buf, len: 0 }
+    }
+
+    /// Write `value` into the next slot of the buffer. Panics if the buffer is full.
+    pub fn push(&mut self, value: U) {
+        self.buf[self.len].write(value);
+        self.len += 1;
+    }
+
+    /// Give up ownership of the written elements, which will no longer be dropped unless the caller does so
+    pub fn leak(self) -> &'b mut [U] {
+        let mut this = ManuallyDrop::new(self);
+        // SAFETY: the first `len` elements have been written, and `this` is never used again so the buffer isn't
+        // aliased.
+        unsafe { std::slice::from_raw_parts_mut(this.buf.as_mut_ptr().cast::<U>(), this.len) }
+    }
+}
+
+impl<U> Deref for InitSlice<'_, U> {
+    type Target = [U];
+
+    fn deref(&self) -> &[U] {
+        // SAFETY: the first `len` elements have been written
+        unsafe { std::slice::from_raw_parts(self.buf.as_ptr().cast::<U>(), self.len) }
+    }
+}
+
+impl<U> DerefMut for InitSlice<'_, U> {
+    fn deref_mut(&mut self) -> &mut [U] {
+        // SAFETY: the first `len` elements have been written
+        unsafe { std::slice::from_raw_parts_mut(self.buf.as_mut_ptr().cast::<U>(), self.len) }
+    }
+}
+
+impl<U> Drop for InitSlice<'_, U> {
+    fn drop(&mut self) {
+        // SAFETY: the first `len` elements have been written and are dropped exactly once
+        unsafe { std::ptr::drop_in_place(&mut **self) }
+    }
+}
+
+impl<U: fmt::Debug> fmt::Debug for InitSlice<'_, U> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        (**self).fmt(f)
+    }
+}
+
+pub trait MapToUninit<F, U>: HList {
+    /// Like [map_to_list](crate::MapToList::map_to_list) but writes the results into the front of `buf`. Panics if
+    /// `buf` is shorter than the HList, dropping the results written so far.
+    fn map_to_uninit(self, f: F, buf: &mut [MaybeUninit<U>]) -> InitSlice<'_, U> {
+        let mut init = InitSlice::new(buf);
+        self.push_mapped(f, &mut init);
+        init
+    }
+
+    fn push_mapped(self, f: F, init: &mut InitSlice<'_, U>);
+}
+
+impl<F, U> MapToUninit<F, U> for HNil {
+    fn push_mapped(self, _f: F, _init: &mut InitSlice<'_, U>) {}
+}
+
+impl<F: Func<Head, Output = U>, U, Head, Tail: MapToUninit<F, U>> MapToUninit<F, U>
+    for HCons<Head, Tail>
+{
+    fn push_mapped(self, mut f: F, init: &mut InitSlice<'_, U>) {
+        let HCons { head, tail } = self;
+        init.push(f.call(head));
+        tail.push_mapped(f, init)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::rc::Rc;
+
+    use frunk::hlist;
+
+    use super::*;
+    use crate::FnPoly;
+
+    struct Widen;
+
+    impl<T: Into<u64>> Func<T> for Widen {
+        type Output = u64;
+
+        fn call(&mut self, i: T) -> u64 {
+            i.into()
+        }
+    }
+
+    #[test]
+    fn map_to_uninit() {
+        let mut buf = [const { MaybeUninit::uninit() }; 4];
+        let init = hlist![1u8, 2u16, 3u32].map_to_uninit(Widen, &mut buf);
+        assert_eq!(*init, [1, 2, 3]);
+        assert_eq!(init.leak(), [1, 2, 3]);
+    }
+
+    #[test]
+    fn map_to_uninit_drops_written_elements() {
+        let counter = Rc::new(());
+        let mut buf = [const { MaybeUninit::uninit() }; 2];
+        let init = hlist![(), ()].map_to_uninit(FnPoly(|()| Rc::clone(&counter)), &mut buf);
+        assert_eq!(Rc::strong_count(&counter), 3);
+        drop(init);
+        assert_eq!(Rc::strong_count(&counter), 1);
+
+        let mut short = [const { MaybeUninit::uninit() }; 1];
+        let overflow = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            hlist![(), ()].map_to_uninit(FnPoly(|()| Rc::clone(&counter)), &mut short);
+        }));
+        assert!(overflow.is_err());
+        assert_eq!(Rc::strong_count(&counter), 1);
+    }
+}
@@ -0,0 +1,114 @@
+//! Error-accumulating traversal of labelled structs. Unlike [try_hmap](crate::WithGeneric::try_hmap), which stops at the
+//! first failure, validation visits every field and reports all of the failures along with the names of the fields
+//! they came from.
+
+use frunk::{
+    labelled::{field_with_name, Field},
+    prelude::HList,
+    HCons, HNil,
+};
+
+use crate::TryFunc;
+
+/// The failures collected by a validation, in field declaration order.
+pub type FieldErrors<E> = Vec<(&'static str, E)>;
+
+pub trait Validate<F, E>: HList {
+    type Output;
+
+    /// Apply `f` to the value of every field. Errors are pushed onto `errors` and the mapped HList is only produced if
+    /// every field succeeded.
+    fn validate(self, f: F, errors: &mut FieldErrors<E>) -> Option<Self::Output>;
+}
+
+impl<F, E> Validate<F, E> for HNil {
+    type Output = HNil;
+
+    fn validate(self, _f: F, _errors: &mut FieldErrors<E>) -> Option<Self::Output> {
+        Some(HNil)
+    }
+}
+
+impl<F: TryFunc<Head, Error = E>, E, Name, Head, Tail: Validate<F, E>> Validate<F, E>
+    for HCons<Field<Name, Head>, Tail>
+{
+    type Output = HCons<Field<Name, F::Output>, <Tail as Validate<F, E>>::Output>;
+
+    fn validate(self, mut f: F, errors: &mut FieldErrors<E>) -> Option<Self::Output> {
+        let HCons { head, tail } = self;
+        let head = match f.try_call(head.value) {
+            Ok(value) => Some(field_with_name(head.name, value)),
+            Err(err) => {
+                errors.push((head.name, err));
+                None
+            }
+        };
+        let tail = tail.validate(f, errors);
+        Some(HCons {
+            head: head?,
+            tail: tail?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use frunk::LabelledGeneric;
+
+    use super::*;
+    use crate::WithLabelledGeneric;
+
+    #[derive(LabelledGeneric)]
+    struct Raw {
+        port: &'static str,
+        host: &'static str,
+        retries: &'static str,
+    }
+
+    #[derive(LabelledGeneric, Debug, PartialEq)]
+    struct Config {
+        port: u16,
+        host: u16,
+        retries: u16,
+    }
+
+    struct Parse;
+
+    impl TryFunc<&'static str> for Parse {
+        type Output = u16;
+        type Error = std::num::ParseIntError;
+
+        fn try_call(&mut self, i: &'static str) -> Result<u16, Self::Error> {
+            i.parse()
+        }
+    }
+
+    #[test]
+    fn collects_all_errors() {
+        let raw = Raw {
+            port: "x",
+            host: "1",
+            retries: "-1",
+        };
+        let errors = raw.validate::<Config, _, _>(Parse).unwrap_err();
+        let names = errors.iter().map(|(name, _)| *name).collect::<Vec<_>>();
+        assert_eq!(names, vec!["port", "retries"]);
+    }
+
+    #[test]
+    fn succeeds_when_all_fields_valid() {
+        let raw = Raw {
+            port: "80",
+            host: "1",
+            retries: "3",
+        };
+        assert_eq!(
+            raw.validate(Parse),
+            Ok(Config {
+                port: 80,
+                host: 1,
+                retries: 3
+            })
+        );
+    }
+}